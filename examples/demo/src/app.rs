@@ -1,4 +1,7 @@
-use super::transition::{bounce::Bounce, fade::Fade, slide_fade::SlideFade};
+use super::transition::{
+    bounce::Bounce, fade::Fade, list::List, route::RouteTransition, slide_fade::SlideFade,
+    view_transition::ViewTransition,
+};
 use leptos::prelude::*;
 use leptos_meta::Style;
 
@@ -19,6 +22,18 @@ pub fn App() -> impl IntoView {
                     <SlideFade />
                     <Bounce />
                 </thaw::Flex>
+                <h1>"TransitionGroup"</h1>
+                <thaw::Flex vertical=true>
+                    <List />
+                </thaw::Flex>
+                <h1>"AnimatedOutlet"</h1>
+                <thaw::Flex vertical=true>
+                    <RouteTransition />
+                </thaw::Flex>
+                <h1>"ViewTransition"</h1>
+                <thaw::Flex vertical=true>
+                    <ViewTransition />
+                </thaw::Flex>
             </main>
         </thaw::ConfigProvider>
     }
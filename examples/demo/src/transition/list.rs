@@ -0,0 +1,60 @@
+use leptos::prelude::*;
+use leptos_meta::Style;
+use leptos_transition_group::{Stagger, TransitionGroup};
+use std::time::Duration;
+
+#[component]
+pub fn List() -> impl IntoView {
+    let items = RwSignal::new((1..=5).collect::<Vec<u32>>());
+    let next_item = StoredValue::new(6);
+
+    let add = move |_| {
+        let item = next_item.get_value();
+        next_item.set_value(item + 1);
+        items.update(|items| items.insert(items.len() / 2, item));
+    };
+    let remove = move |_| {
+        items.update(|items| {
+            if !items.is_empty() {
+                items.remove(items.len() / 2);
+            }
+        })
+    };
+    let clear = move |_| items.set(vec![]);
+    let reset = move |_| items.set((1..=5).collect());
+
+    view! {
+        <thaw::Card>
+            <thaw::CardHeader>"list"</thaw::CardHeader>
+            <Style>
+                ".list-enter-active,
+                .list-leave-active {
+                    transition: all 0.5s ease;
+                }
+
+                .list-enter-from,
+                .list-leave-to {
+                    opacity: 0;
+                    transform: translateX(30px);
+                }"
+            </Style>
+            <thaw::Flex>
+                <thaw::Button on_click=add>"Add"</thaw::Button>
+                <thaw::Button on_click=remove>"Remove"</thaw::Button>
+                <thaw::Button on_click=clear>"Clear"</thaw::Button>
+                <thaw::Button on_click=reset>"Reset"</thaw::Button>
+            </thaw::Flex>
+            <ul>
+                <TransitionGroup
+                    each=move || items.get()
+                    key=|item| *item
+                    name="list"
+                    stagger=Stagger::new(Duration::from_millis(60))
+                    let:item
+                >
+                    <li>{item}</li>
+                </TransitionGroup>
+            </ul>
+        </thaw::Card>
+    }
+}
@@ -1,3 +1,6 @@
 pub mod bounce;
 pub mod fade;
+pub mod list;
+pub mod route;
 pub mod slide_fade;
+pub mod view_transition;
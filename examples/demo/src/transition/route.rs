@@ -0,0 +1,48 @@
+use leptos::prelude::*;
+use leptos_meta::Style;
+use leptos_router::{
+    components::{ParentRoute, Route, Router, Routes, A},
+    path,
+};
+use leptos_transition_group::{AnimatedOutlet, TransitionMode};
+
+#[component]
+pub fn RouteTransition() -> impl IntoView {
+    view! {
+        <thaw::Card>
+            <thaw::CardHeader>"route"</thaw::CardHeader>
+            <Style>
+                ".route-enter-active,
+                .route-leave-active {
+                    transition: opacity 0.3s ease, transform 0.3s ease;
+                }
+
+                .route-enter-from {
+                    opacity: 0;
+                    transform: translateY(10px);
+                }
+
+                .route-leave-to {
+                    opacity: 0;
+                    transform: translateY(-10px);
+                }"
+            </Style>
+            <Router>
+                <nav>
+                    <A href="/">"Home"</A>
+                    " | "
+                    <A href="/about">"About"</A>
+                </nav>
+                <Routes fallback=|| "Not found.">
+                    <ParentRoute
+                        path=path!("")
+                        view=|| view! { <AnimatedOutlet name="route" mode=TransitionMode::OutIn /> }
+                    >
+                        <Route path=path!("") view=|| view! { <p>"Home page"</p> } />
+                        <Route path=path!("about") view=|| view! { <p>"About page"</p> } />
+                    </ParentRoute>
+                </Routes>
+            </Router>
+        </thaw::Card>
+    }
+}
@@ -0,0 +1,50 @@
+use leptos::prelude::*;
+use leptos_meta::Style;
+use leptos_transition_group::{start_view_transition, CSSTransition};
+
+#[component]
+pub fn ViewTransition() -> impl IntoView {
+    let show = RwSignal::new(false);
+    let expanded = RwSignal::new(false);
+
+    view! {
+        <thaw::Card>
+            <thaw::CardHeader>"view-transition"</thaw::CardHeader>
+            <Style>
+                ".fallback-enter-active,
+                .fallback-leave-active {
+                    transition: opacity 0.5s ease;
+                }
+
+                .fallback-enter-from,
+                .fallback-leave-to {
+                    opacity: 0;
+                }
+
+                .view-transition-box {
+                    view-transition-name: view-transition-box;
+                    background: #0f6cbd;
+                    height: 40px;
+                    width: 40px;
+                }
+
+                .view-transition-box.expanded {
+                    width: 200px;
+                }"
+            </Style>
+            <thaw::Button on_click=move |_| show.update(|v| *v = !*v)>"Toggle"</thaw::Button>
+            <CSSTransition
+                show=show
+                name="fallback"
+                view_transition=true
+                view_transition_name="view-transition-text"
+            >
+                <p>"hello"</p>
+            </CSSTransition>
+            <thaw::Button on_click=move |_| {
+                start_view_transition(move || expanded.update(|v| *v = !*v));
+            }>"Resize"</thaw::Button>
+            <div class="view-transition-box" class:expanded=move || expanded.get()></div>
+        </thaw::Card>
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    play_transition::{play_transition, PlayTransition},
+    scheduler::Scheduler,
+    utils::element_style,
+    TransitionMode, TransitionPhase,
+};
+use leptos::{html, prelude::*};
+use leptos_router::components::Outlet;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use web_sys::{
+    js_sys::Array,
+    wasm_bindgen::{closure::Closure, JsCast},
+    Element, MutationObserver, MutationObserverInit, MutationRecord, Node,
+};
+
+/// # AnimatedOutlet
+///
+/// Used in place of leptos_router's `<Outlet/>` to apply enter and leave
+/// animations when the matched child route changes.
+///
+/// The root elements of the outgoing route are kept mounted until their
+/// leave transition ends. In `TransitionMode::Simultaneous` both routes are
+/// in the layout at the same time, so the leaving one usually needs
+/// `position: absolute` in its `*-leave-active` class.
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use leptos_router::{components::*, path};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <ParentRoute
+///                 path=path!("")
+///                 view=|| view! { <AnimatedOutlet name="fade" mode=TransitionMode::OutIn /> }
+///             >
+///                 <Route path=path!("") view=|| view! { <p>"Home"</p> } />
+///                 <Route path=path!("about") view=|| view! { <p>"About"</p> } />
+///             </ParentRoute>
+///         </Routes>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn AnimatedOutlet(
+    /// Used to automatically generate transition CSS class names.
+    #[prop(into)]
+    name: Signal<String>,
+    /// The order of the leave and enter transitions.
+    #[prop(optional)]
+    mode: TransitionMode,
+) -> impl IntoView {
+    let container_ref = NodeRef::<html::Div>::new();
+    let scheduler = Scheduler::current();
+
+    Effect::new(move |_| {
+        let Some(container) = container_ref.get() else {
+            return;
+        };
+        let container = Node::from(container);
+
+        let running = Rc::new(RefCell::new(Vec::<PlayTransition>::new()));
+        let waiting = Rc::new(RefCell::new(Vec::<Element>::new()));
+        let generation = Rc::new(Cell::new(0usize));
+        // Leaving elements that have been removed by us.
+        let ghosts = Rc::new(RefCell::new(Vec::<Element>::new()));
+
+        let on_mutation = {
+            let container = container.clone();
+            let scheduler = scheduler.clone();
+            move |records: Array, observer: MutationObserver| {
+                let name = name.get_untracked();
+
+                let mut removed = vec![];
+                let mut added = vec![];
+                for record in records.iter() {
+                    let record = record.unchecked_into::<MutationRecord>();
+                    let removed_nodes = record.removed_nodes();
+                    for i in 0..removed_nodes.length() {
+                        if let Some(el) = removed_nodes
+                            .item(i)
+                            .and_then(|node| node.dyn_into::<Element>().ok())
+                        {
+                            removed.push((el, record.next_sibling()));
+                        }
+                    }
+                    let added_nodes = record.added_nodes();
+                    for i in 0..added_nodes.length() {
+                        if let Some(el) = added_nodes
+                            .item(i)
+                            .and_then(|node| node.dyn_into::<Element>().ok())
+                        {
+                            added.push(el);
+                        }
+                    }
+                }
+                removed.retain(|(el, _)| {
+                    let mut ghosts = ghosts.borrow_mut();
+                    if let Some(index) = ghosts.iter().position(|ghost| ghost == el) {
+                        ghosts.remove(index);
+                        return false;
+                    }
+                    !el.is_connected()
+                });
+                added.retain(|el| el.parent_node().as_ref() == Some(&container));
+                if removed.is_empty() && added.is_empty() {
+                    return;
+                }
+
+                // Jumps to the end of the previous navigation.
+                generation.set(generation.get() + 1);
+                let prev = running.borrow_mut().drain(..).collect::<Vec<_>>();
+                for transition in prev {
+                    transition.complete();
+                }
+                for el in waiting.borrow_mut().drain(..) {
+                    if let Some(style) = element_style(&el) {
+                        let _ = style.remove_property("display");
+                    }
+                }
+
+                let enter = {
+                    let scheduler = scheduler.clone();
+                    let running = running.clone();
+                    let name = name.clone();
+                    move |added: Vec<Element>| {
+                        for el in added {
+                            let transition = play_transition(
+                                &scheduler,
+                                el,
+                                &name,
+                                TransitionPhase::Enter,
+                                || {},
+                            );
+                            running.borrow_mut().push(transition);
+                        }
+                    }
+                };
+
+                let leave_count = Rc::new(Cell::new(removed.len()));
+                let on_leave_end = {
+                    let generation_value = generation.get();
+                    let generation = generation.clone();
+                    let waiting = waiting.clone();
+                    let enter = enter.clone();
+                    move || {
+                        leave_count.set(leave_count.get() - 1);
+                        if leave_count.get() > 0 || generation.get() != generation_value {
+                            return;
+                        }
+                        let waiting = waiting.borrow_mut().drain(..).collect::<Vec<_>>();
+                        for el in waiting.iter() {
+                            if let Some(style) = element_style(el) {
+                                let _ = style.remove_property("display");
+                            }
+                        }
+                        enter(waiting);
+                    }
+                };
+
+                for (el, next_sibling) in removed {
+                    let next_sibling =
+                        next_sibling.filter(|node| node.parent_node().as_ref() == Some(&container));
+                    let _ = container.insert_before(&el, next_sibling.as_ref());
+                    let transition =
+                        play_transition(&scheduler, el.clone(), &name, TransitionPhase::Leave, {
+                            let ghosts = ghosts.clone();
+                            let on_leave_end = on_leave_end.clone();
+                            move || {
+                                el.remove();
+                                ghosts.borrow_mut().push(el);
+                                on_leave_end();
+                            }
+                        });
+                    running.borrow_mut().push(transition);
+                }
+
+                if mode == TransitionMode::OutIn && !running.borrow().is_empty() {
+                    for el in added.iter() {
+                        if let Some(style) = element_style(el) {
+                            let _ = style.set_property("display", "none");
+                        }
+                    }
+                    *waiting.borrow_mut() = added;
+                } else {
+                    enter(added);
+                }
+
+                // Ignores the mutations made above.
+                observer.take_records();
+                ghosts.borrow_mut().clear();
+            }
+        };
+
+        let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new(on_mutation);
+        let Ok(observer) = MutationObserver::new(callback.as_ref().unchecked_ref()) else {
+            return;
+        };
+        let options = MutationObserverInit::new();
+        options.set_child_list(true);
+        let _ = observer.observe_with_options(&container, &options);
+
+        let observer = send_wrapper::SendWrapper::new((observer, callback));
+        on_cleanup(move || {
+            let (observer, _callback) = observer.take();
+            observer.disconnect();
+        });
+    });
+
+    view! {
+        <div node_ref=container_ref>
+            <Outlet />
+        </div>
+    }
+}
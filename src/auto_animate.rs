@@ -0,0 +1,268 @@
+use crate::utils::{pin_in_place, Rect};
+use leptos::{html::ElementType, prelude::*};
+use send_wrapper::SendWrapper;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use web_sys::{
+    js_sys::{Array, Function, Object, Reflect},
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    Element, HtmlElement, MutationObserver, MutationObserverInit, MutationRecord, Node,
+};
+
+/// The animations of [`use_auto_animate_with_options`].
+///
+/// Keyframe properties are named as in `Element.animate()`, e.g.
+/// `("transform", "scale(0.95)")`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoAnimateOptions {
+    pub duration: Duration,
+    /// A CSS easing function.
+    pub easing: String,
+    /// The keyframe added children enter from.
+    pub enter: Vec<(String, String)>,
+    /// The keyframe removed children leave to.
+    pub leave: Vec<(String, String)>,
+}
+
+impl Default for AutoAnimateOptions {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(250),
+            easing: "ease-in-out".to_string(),
+            enter: vec![("opacity".to_string(), "0".to_string())],
+            leave: vec![("opacity".to_string(), "0".to_string())],
+        }
+    }
+}
+
+/// Animates the children of the element of `node_ref` without any
+/// configuration: added children fade in, removed children fade out and
+/// children that move because of them slide to their new position.
+///
+/// Only the direct children of the element that are HTML elements are
+/// animated, SVG children are laid out without animation. Removed children
+/// are put back in place with `position: absolute` until they have faded
+/// out, so the element gets `position: relative` if it isn't positioned.
+///
+/// ``` rust
+/// use leptos::{html, prelude::*};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let items = RwSignal::new(vec![1, 2, 3]);
+/// let list_ref = NodeRef::<html::Ul>::new();
+/// use_auto_animate(list_ref);
+/// view! {
+///     <ul node_ref=list_ref>
+///         <For each=move || items.get() key=|item| *item let:item>
+///             <li>{item}</li>
+///         </For>
+///     </ul>
+/// }
+/// # }
+/// ```
+pub fn use_auto_animate<E>(node_ref: NodeRef<E>)
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    use_auto_animate_with_options(node_ref, AutoAnimateOptions::default());
+}
+
+/// Same as [`use_auto_animate`], with custom timings and keyframes.
+pub fn use_auto_animate_with_options<E>(node_ref: NodeRef<E>, options: AutoAnimateOptions)
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    Effect::new(move |_| {
+        let Some(container) = node_ref.get() else {
+            return;
+        };
+        let container = container.unchecked_into::<Element>();
+        if let Some(container) = container.dyn_ref::<HtmlElement>() {
+            let position = window()
+                .get_computed_style(container)
+                .ok()
+                .flatten()
+                .and_then(|styles| styles.get_property_value("position").ok());
+            if position.as_deref() == Some("static") {
+                let _ = container.style().set_property("position", "relative");
+            }
+        }
+
+        let auto_animate = Rc::new(AutoAnimate {
+            container: container.clone(),
+            options: options.clone(),
+            rects: Default::default(),
+            ghosts: Default::default(),
+        });
+        auto_animate.measure();
+
+        let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new({
+            let auto_animate = auto_animate.clone();
+            move |records: Array, observer: MutationObserver| {
+                auto_animate.on_mutation(records);
+                // Ignores the ghosts inserted above.
+                observer.take_records();
+            }
+        });
+        let Ok(observer) = MutationObserver::new(callback.as_ref().unchecked_ref()) else {
+            return;
+        };
+        let init = MutationObserverInit::new();
+        init.set_child_list(true);
+        let _ = observer.observe_with_options(&container, &init);
+
+        let observer = SendWrapper::new((observer, callback));
+        on_cleanup(move || {
+            let (observer, _callback) = observer.take();
+            observer.disconnect();
+        });
+    });
+}
+
+struct AutoAnimate {
+    container: Element,
+    options: AutoAnimateOptions,
+    /// The children and where they were laid out after the last mutation.
+    rects: RefCell<Vec<(Element, Rect)>>,
+    /// Removed children that are fading out.
+    ghosts: RefCell<Vec<Element>>,
+}
+
+impl AutoAnimate {
+    fn children(&self) -> Vec<Element> {
+        let children = self.container.children();
+        (0..children.length())
+            .filter_map(|i| children.item(i))
+            .filter(|child| !self.ghosts.borrow().contains(child))
+            .collect()
+    }
+
+    fn measure(&self) {
+        *self.rects.borrow_mut() = self
+            .children()
+            .into_iter()
+            .filter_map(|child| Some((child.clone(), Rect::measure(child.dyn_ref()?))))
+            .collect();
+    }
+
+    fn prev_rect(&self, el: &Element) -> Option<Rect> {
+        self.rects
+            .borrow()
+            .iter()
+            .find(|(child, _)| child == el)
+            .map(|(_, rect)| *rect)
+    }
+
+    fn on_mutation(&self, records: Array) {
+        let mut removed = vec![];
+        for record in records.iter() {
+            let record = record.unchecked_into::<MutationRecord>();
+            let removed_nodes = record.removed_nodes();
+            for i in 0..removed_nodes.length() {
+                let Some(el) = removed_nodes
+                    .item(i)
+                    .and_then(|node| node.dyn_into::<Element>().ok())
+                else {
+                    continue;
+                };
+                let mut ghosts = self.ghosts.borrow_mut();
+                if let Some(index) = ghosts.iter().position(|ghost| *ghost == el) {
+                    ghosts.remove(index);
+                } else if el.parent_node().is_none() {
+                    removed.push((el, record.next_sibling()));
+                }
+            }
+        }
+
+        for (el, next_sibling) in removed {
+            if let Some(rect) = self.prev_rect(&el) {
+                self.leave(el, next_sibling, rect);
+            }
+        }
+
+        for child in self.children() {
+            let Some(rect) = child.dyn_ref::<HtmlElement>().map(Rect::measure) else {
+                continue;
+            };
+            match self.prev_rect(&child) {
+                Some(prev) if prev != rect => self.move_to(&child, prev, rect),
+                Some(_) => {}
+                None => {
+                    self.animate(&child, &[self.options.enter.clone(), vec![]]);
+                }
+            }
+        }
+        self.measure();
+    }
+
+    fn leave(&self, el: Element, next_sibling: Option<Node>, rect: Rect) {
+        let container: &Node = self.container.as_ref();
+        let next_sibling =
+            next_sibling.filter(|node| node.parent_node().as_ref() == Some(container));
+        if container.insert_before(&el, next_sibling.as_ref()).is_err() {
+            return;
+        }
+        if let Some(html) = el.dyn_ref::<HtmlElement>() {
+            pin_in_place(html, rect);
+            let _ = html.style().set_property("pointer-events", "none");
+        }
+        self.ghosts.borrow_mut().push(el.clone());
+
+        let Some(animation) = self.animate(&el, &[vec![], self.options.leave.clone()]) else {
+            el.remove();
+            return;
+        };
+        let on_finish = Closure::once_into_js(move || el.remove());
+        let _ = Reflect::set(&animation, &JsValue::from_str("onfinish"), &on_finish);
+    }
+
+    fn move_to(&self, el: &Element, prev: Rect, rect: Rect) {
+        let scale = |prev: f64, next: f64| if next == 0.0 { 1.0 } else { prev / next };
+        let transform = format!(
+            "translate({}px, {}px) scale({}, {})",
+            prev.left - rect.left,
+            prev.top - rect.top,
+            scale(prev.width, rect.width),
+            scale(prev.height, rect.height),
+        );
+        let origin = ("transformOrigin".to_string(), "0 0".to_string());
+        self.animate(
+            el,
+            &[
+                vec![("transform".to_string(), transform), origin.clone()],
+                vec![("transform".to_string(), "none".to_string()), origin],
+            ],
+        );
+    }
+
+    /// Calls `Element.animate()`, returning the `Animation`.
+    fn animate(&self, el: &Element, keyframes: &[Vec<(String, String)>]) -> Option<JsValue> {
+        let keyframes = keyframes
+            .iter()
+            .map(|keyframe| {
+                let object = Object::new();
+                for (property, value) in keyframe {
+                    let _ = Reflect::set(&object, &property.into(), &value.into());
+                }
+                JsValue::from(object)
+            })
+            .collect::<Array>();
+        let options = Object::new();
+        let _ = Reflect::set(
+            &options,
+            &JsValue::from_str("duration"),
+            &JsValue::from_f64(self.options.duration.as_secs_f64() * 1000.0),
+        );
+        let _ = Reflect::set(
+            &options,
+            &JsValue::from_str("easing"),
+            &JsValue::from_str(&self.options.easing),
+        );
+        let animate = Reflect::get(el, &JsValue::from_str("animate"))
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        animate.call2(el, &keyframes, &options).ok()
+    }
+}
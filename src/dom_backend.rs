@@ -0,0 +1,362 @@
+use crate::{
+    get_transition_info::get_transition_info,
+    hide_strategy::HideStrategy,
+    scheduler::{ScheduleHandle, Scheduler},
+    time_scale::scale_running,
+    transition_a11y::TransitionA11y,
+    transition_info_cache::get_transition_info_cached,
+    transition_machine::{TransitionBackend, TransitionContext, TransitionHook, TransitionStage},
+    utils::{add_event_listener, element_style, EventListenerHandle},
+    AnimationTypes, CSSTransitionInfo, TransitionController, TransitionEvent, TransitionHooks,
+    TransitionOutcome, TransitionPhase, TransitionStatus,
+};
+use leptos::{
+    ev,
+    prelude::{document, GetUntracked, Signal},
+};
+use std::{cell::RefCell, sync::Arc, time::Duration};
+use web_sys::{js_sys::Array, wasm_bindgen::JsValue, Element, EventTarget};
+
+/// The [`TransitionBackend`] of `CSSTransition`.
+pub(crate) struct DomBackend {
+    pub el: Element,
+    pub scheduler: Scheduler,
+    pub hooks: Arc<dyn TransitionHooks>,
+    pub controller: Option<TransitionController>,
+    pub cache_info: bool,
+    /// CSS custom properties set while a transition is running.
+    pub vars: Option<Signal<Vec<(String, String)>>>,
+    /// The names of the custom properties currently set.
+    pub applied_vars: RefCell<Vec<String>>,
+    pub stage_styles: Arc<StageStyles>,
+    pub hide_strategy: HideStrategy,
+    pub a11y: TransitionA11y,
+    /// The element focused before the enter transition.
+    pub focus_origin: RefCell<Option<Element>>,
+}
+
+/// Inline styles applied along with the class of each stage.
+#[derive(Default)]
+pub(crate) struct StageStyles {
+    pub enter_from: Vec<(String, String)>,
+    pub enter_active: Vec<(String, String)>,
+    pub enter_to: Vec<(String, String)>,
+    pub leave_from: Vec<(String, String)>,
+    pub leave_active: Vec<(String, String)>,
+    pub leave_to: Vec<(String, String)>,
+}
+
+impl StageStyles {
+    fn get(&self, phase: TransitionPhase, stage: TransitionStage) -> &[(String, String)] {
+        match (phase, stage) {
+            (TransitionPhase::Enter, TransitionStage::From) => &self.enter_from,
+            (TransitionPhase::Enter, TransitionStage::Active) => &self.enter_active,
+            (TransitionPhase::Enter, TransitionStage::To) => &self.enter_to,
+            (TransitionPhase::Leave, TransitionStage::From) => &self.leave_from,
+            (TransitionPhase::Leave, TransitionStage::Active) => &self.leave_active,
+            (TransitionPhase::Leave, TransitionStage::To) => &self.leave_to,
+        }
+    }
+}
+
+impl DomBackend {
+    /// Sets the current `vars` on the element, and removes the ones that
+    /// are no longer part of them.
+    pub fn apply_vars(&self) {
+        let Some(vars) = self.vars else {
+            return;
+        };
+        let Some(style) = element_style(&self.el) else {
+            return;
+        };
+        let vars = vars.get_untracked();
+        for name in self.applied_vars.borrow().iter() {
+            if !vars.iter().any(|(var, _)| var == name) {
+                let _ = style.remove_property(name);
+            }
+        }
+        for (name, value) in &vars {
+            let _ = style.set_property(name, value);
+        }
+        *self.applied_vars.borrow_mut() = vars.into_iter().map(|(name, _)| name).collect();
+    }
+
+    fn remove_vars(&self) {
+        let Some(style) = element_style(&self.el) else {
+            return;
+        };
+        for name in self.applied_vars.take() {
+            let _ = style.remove_property(&name);
+        }
+    }
+}
+
+pub(crate) enum DomHandle {
+    Schedule(ScheduleHandle),
+    Listener(EventListenerHandle),
+}
+
+impl TransitionBackend for DomBackend {
+    type Handle = DomHandle;
+
+    fn add_classes(&self, classes: &[&str]) {
+        let _ = self.el.class_list().add(&to_array(classes));
+    }
+
+    fn remove_classes(&self, classes: &[&str]) {
+        let _ = self.el.class_list().remove(&to_array(classes));
+    }
+
+    fn change_stages(
+        &self,
+        phase: TransitionPhase,
+        removed: &[TransitionStage],
+        added: &[TransitionStage],
+    ) {
+        let Some(style) = element_style(&self.el) else {
+            return;
+        };
+        for stage in removed {
+            for (name, _) in self.stage_styles.get(phase, *stage) {
+                let _ = style.remove_property(name);
+            }
+        }
+        for stage in added {
+            for (name, value) in self.stage_styles.get(phase, *stage) {
+                let _ = style.set_property(name, value);
+            }
+        }
+    }
+
+    fn set_visible(&self, visible: bool) {
+        self.hide_strategy.set_visible(&self.el, visible);
+    }
+
+    fn force_reflow(&self) {
+        // Reading a layout property flushes the pending styles, like Vue.
+        if let Some(body) = document().body() {
+            let _ = body.offset_height();
+        }
+    }
+
+    fn transition_info(&self) -> Option<CSSTransitionInfo> {
+        transition_info(&self.el, &self.scheduler, self.cache_info)
+    }
+
+    fn request_frame(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.request_frame(cb))
+    }
+
+    fn request_read(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.request_read(cb))
+    }
+
+    fn set_timeout(&self, cb: Box<dyn FnOnce()>, delay: Duration) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.set_timeout(cb, delay))
+    }
+
+    fn listen_end(&self, types: AnimationTypes, cb: Box<dyn Fn()>) -> Self::Handle {
+        listen_end(&self.el, types, cb)
+    }
+
+    fn cancel(&self, handle: Self::Handle) {
+        handle.cancel();
+    }
+
+    fn describe(&self) -> String {
+        describe(&self.el)
+    }
+
+    fn notify(&self, hook: TransitionHook, context: &TransitionContext) {
+        let event = TransitionEvent {
+            el: self.el.clone(),
+            phase: context.phase,
+            appear: context.appear,
+            interrupted: context.interrupted,
+            info: context.info,
+        };
+        match hook {
+            TransitionHook::BeforeEnter | TransitionHook::BeforeLeave => self.apply_vars(),
+            TransitionHook::AfterEnter
+            | TransitionHook::EnterCancelled
+            | TransitionHook::AfterLeave
+            | TransitionHook::LeaveCancelled => {
+                self.remove_vars();
+            }
+            TransitionHook::Enter | TransitionHook::Leave => {}
+        }
+        match hook {
+            TransitionHook::BeforeEnter | TransitionHook::LeaveCancelled => {
+                self.a11y.set_hidden(&self.el, false);
+                if hook == TransitionHook::BeforeEnter && !context.interrupted {
+                    *self.focus_origin.borrow_mut() = self.a11y.focus_origin(&self.el);
+                }
+            }
+            TransitionHook::AfterEnter => self.a11y.autofocus_in(&self.el),
+            TransitionHook::BeforeLeave => self.a11y.set_hidden(&self.el, true),
+            TransitionHook::AfterLeave => self
+                .a11y
+                .restore_focus_from(&self.el, self.focus_origin.take()),
+            _ => {}
+        }
+
+        let hooks = &self.hooks;
+        match hook {
+            TransitionHook::BeforeEnter => hooks.before_enter(&event),
+            TransitionHook::Enter => hooks.enter(&event),
+            TransitionHook::AfterEnter => hooks.after_enter(&event),
+            TransitionHook::EnterCancelled => hooks.enter_cancelled(&event),
+            TransitionHook::BeforeLeave => hooks.before_leave(&event),
+            TransitionHook::Leave => hooks.leave(&event),
+            TransitionHook::AfterLeave => hooks.after_leave(&event),
+            TransitionHook::LeaveCancelled => hooks.leave_cancelled(&event),
+        }
+
+        if let Some(controller) = self.controller {
+            controller.set_status(match hook {
+                TransitionHook::BeforeEnter | TransitionHook::Enter => TransitionStatus::Entering,
+                TransitionHook::BeforeLeave | TransitionHook::Leave => TransitionStatus::Leaving,
+                TransitionHook::AfterEnter
+                | TransitionHook::EnterCancelled
+                | TransitionHook::LeaveCancelled => TransitionStatus::Entered,
+                TransitionHook::AfterLeave => TransitionStatus::Left,
+            });
+            match hook {
+                TransitionHook::AfterEnter => controller.settle(true, TransitionOutcome::Finished),
+                TransitionHook::AfterLeave => controller.settle(false, TransitionOutcome::Finished),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A [`TransitionBackend`] that only plays the classes on an element, for
+/// transitions outside of `CSSTransition`. The element is never hidden.
+pub(crate) struct ElementBackend {
+    pub el: Element,
+    pub scheduler: Scheduler,
+    /// Called when the transition has ended.
+    pub on_end: RefCell<Option<Box<dyn FnOnce()>>>,
+}
+
+impl TransitionBackend for ElementBackend {
+    type Handle = DomHandle;
+
+    fn add_classes(&self, classes: &[&str]) {
+        let _ = self.el.class_list().add(&to_array(classes));
+    }
+
+    fn remove_classes(&self, classes: &[&str]) {
+        let _ = self.el.class_list().remove(&to_array(classes));
+    }
+
+    fn set_visible(&self, _visible: bool) {}
+
+    fn transition_info(&self) -> Option<CSSTransitionInfo> {
+        transition_info(&self.el, &self.scheduler, false)
+    }
+
+    fn request_frame(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.request_frame(cb))
+    }
+
+    fn request_read(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.request_read(cb))
+    }
+
+    fn set_timeout(&self, cb: Box<dyn FnOnce()>, delay: Duration) -> Self::Handle {
+        DomHandle::Schedule(self.scheduler.set_timeout(cb, delay))
+    }
+
+    fn listen_end(&self, types: AnimationTypes, cb: Box<dyn Fn()>) -> Self::Handle {
+        listen_end(&self.el, types, cb)
+    }
+
+    fn cancel(&self, handle: Self::Handle) {
+        handle.cancel();
+    }
+
+    fn describe(&self) -> String {
+        describe(&self.el)
+    }
+
+    fn notify(&self, hook: TransitionHook, _context: &TransitionContext) {
+        match hook {
+            TransitionHook::AfterEnter | TransitionHook::AfterLeave => {
+                if let Some(on_end) = self.on_end.take() {
+                    on_end();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DomHandle {
+    pub fn cancel(self) {
+        match self {
+            Self::Schedule(handle) => handle.cancel(),
+            Self::Listener(handle) => handle.remove(),
+        }
+    }
+}
+
+/// The transition the element has, or the one set on the mock scheduler
+/// in tests, scaled by the time scale.
+fn transition_info(
+    el: &Element,
+    scheduler: &Scheduler,
+    cache_info: bool,
+) -> Option<CSSTransitionInfo> {
+    #[cfg(any(test, feature = "testing"))]
+    if let Scheduler::Mock(scheduler) = scheduler {
+        if let Some(info) = scheduler.transition_info() {
+            return info;
+        }
+    }
+    #[cfg(not(any(test, feature = "testing")))]
+    let _ = scheduler;
+
+    let info = if cache_info {
+        get_transition_info_cached(el)
+    } else {
+        get_transition_info(el)
+    };
+    scale_running(el, info)
+}
+
+// Ignores the end events bubbling up from descendants.
+fn listen_end(el: &Element, types: AnimationTypes, cb: Box<dyn Fn()>) -> DomHandle {
+    let on_end = {
+        let el = el.clone();
+        move |target: Option<EventTarget>| {
+            if target.as_ref() == Some(el.as_ref()) {
+                cb();
+            }
+        }
+    };
+    let el = el.clone();
+    DomHandle::Listener(match types {
+        AnimationTypes::Transition => {
+            add_event_listener(el, ev::transitionend, move |ev| on_end(ev.target()))
+        }
+        AnimationTypes::Animation => {
+            add_event_listener(el, ev::animationend, move |ev| on_end(ev.target()))
+        }
+    })
+}
+
+fn describe(el: &Element) -> String {
+    let tag = el.tag_name().to_lowercase();
+    match el.id() {
+        id if id.is_empty() => tag,
+        id => format!("{tag}#{id}"),
+    }
+}
+
+fn to_array(classes: &[&str]) -> Array {
+    classes
+        .iter()
+        .map(|class| JsValue::from_str(class))
+        .collect()
+}
@@ -0,0 +1,162 @@
+use crate::{
+    play_transition::play_transition,
+    scheduler::Scheduler,
+    utils::{pin_in_place, Rect},
+    TransitionPhase,
+};
+use leptos::{html::ElementType, prelude::*};
+use send_wrapper::SendWrapper;
+use std::{cell::RefCell, rc::Rc};
+use web_sys::{
+    js_sys::Array,
+    wasm_bindgen::{closure::Closure, JsCast},
+    Element, HtmlElement, MutationObserver, MutationObserverInit, MutationRecord, Node,
+};
+
+type RemovalObserver = (
+    MutationObserver,
+    Closure<dyn FnMut(Array, MutationObserver)>,
+);
+
+/// Plays a leave animation when the element of `node_ref` is removed by
+/// its parent view, e.g. by `<Show>`, a `match` or `<For>`, which give no
+/// chance to delay the removal.
+///
+/// A deep clone of the element, the ghost, is put back in its place,
+/// positioned absolutely over it. The `{name}-leave-*` classes are played
+/// on the ghost, which is removed when the animation ends. The ghost is
+/// `inert` and only shows what the element looked like.
+///
+/// Only a direct removal of the element from its parent is caught, not the
+/// removal of one of its ancestors.
+///
+/// ``` rust
+/// use leptos::{html, prelude::*};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let show = RwSignal::new(true);
+/// view! {
+///     <Show when=move || show.get()>
+///         {
+///             let node_ref = NodeRef::<html::Div>::new();
+///             use_exit_ghost(node_ref, "fade");
+///             view! { <div node_ref=node_ref>"hello"</div> }
+///         }
+///     </Show>
+/// }
+/// # }
+/// ```
+pub fn use_exit_ghost<E>(node_ref: NodeRef<E>, name: impl Into<Signal<String>>)
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let name = name.into();
+    let scheduler = Scheduler::current();
+
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        let Some(parent) = el.parent_node() else {
+            return;
+        };
+
+        let observer = Rc::new(RefCell::new(None::<RemovalObserver>));
+        let disconnect = {
+            let observer = observer.clone();
+            move || {
+                if let Some((observer, _callback)) = observer.borrow_mut().take() {
+                    observer.disconnect();
+                }
+            }
+        };
+
+        let on_mutation = {
+            let el = el.clone();
+            let parent = parent.clone();
+            let scheduler = scheduler.clone();
+            let disconnect = disconnect.clone();
+            move |records: Array, _: MutationObserver| {
+                if el.is_connected() {
+                    return;
+                }
+                let next_sibling = records.iter().find_map(|record| {
+                    let record = record.unchecked_into::<MutationRecord>();
+                    let removed_nodes = record.removed_nodes();
+                    (0..removed_nodes.length())
+                        .filter_map(|i| removed_nodes.item(i))
+                        .any(|node| node == *el)
+                        .then(|| record.next_sibling())
+                });
+                let Some(next_sibling) = next_sibling else {
+                    return;
+                };
+                disconnect();
+                spawn_ghost(
+                    &scheduler,
+                    &el,
+                    &parent,
+                    next_sibling,
+                    &name.get_untracked(),
+                );
+            }
+        };
+
+        let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new(on_mutation);
+        let Ok(mutation_observer) = MutationObserver::new(callback.as_ref().unchecked_ref()) else {
+            return;
+        };
+        let options = MutationObserverInit::new();
+        options.set_child_list(true);
+        let _ = mutation_observer.observe_with_options(&parent, &options);
+        *observer.borrow_mut() = Some((mutation_observer, callback));
+
+        // The owner is usually disposed right before the element is removed.
+        // Once the removal is done, the observer is only kept if it has a
+        // record of it.
+        let check = SendWrapper::new(move || {
+            if el.parent_node().is_some() {
+                disconnect();
+            }
+        });
+        on_cleanup(move || {
+            queue_microtask(move || check.take()());
+        });
+    });
+}
+
+fn spawn_ghost(
+    scheduler: &Scheduler,
+    el: &Element,
+    parent: &Node,
+    next_sibling: Option<Node>,
+    name: &str,
+) {
+    let Ok(ghost) = el.clone_node_with_deep(true) else {
+        return;
+    };
+    let ghost = ghost.unchecked_into::<Element>();
+    let next_sibling = next_sibling.filter(|node| node.parent_node().as_ref() == Some(parent));
+    if parent.insert_before(&ghost, next_sibling.as_ref()).is_err() {
+        return;
+    }
+
+    // Measured in the flow, before it is painted.
+    if let Some(html) = ghost.dyn_ref::<HtmlElement>() {
+        pin_in_place(html, Rect::measure(html));
+    }
+    let _ = ghost.set_attribute("inert", "");
+    let _ = ghost.set_attribute("aria-hidden", "true");
+
+    play_transition(
+        scheduler,
+        ghost.clone(),
+        name,
+        TransitionPhase::Leave,
+        move || {
+            ghost.remove();
+        },
+    );
+}
@@ -1,14 +1,17 @@
 use leptos::prelude::window;
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnimationTypes {
     Transition,
     Animation,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CSSTransitionInfo {
     pub types: AnimationTypes,
+    /// The number of transitioned properties or animations.
     pub prop_count: usize,
+    /// The longest duration plus delay, in milliseconds.
     pub timeout: u64,
 }
 
@@ -49,7 +52,7 @@ pub fn get_transition_info(el: &web_sys::Element) -> Option<CSSTransitionInfo> {
     })
 }
 
-fn get_timeout(mut delays: Vec<String>, durations: &Vec<String>) -> u64 {
+fn get_timeout(mut delays: Vec<String>, durations: &[String]) -> u64 {
     while delays.len() < durations.len() {
         delays.append(&mut delays.clone())
     }
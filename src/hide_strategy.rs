@@ -0,0 +1,97 @@
+use crate::utils::{add_event_listener_untyped, element_style, EventListenerHandle};
+use web_sys::{wasm_bindgen::JsCast, Element, HtmlDialogElement, HtmlElement};
+
+/// How the element is shown and hidden around its transitions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HideStrategy {
+    /// Toggles `display: none`.
+    #[default]
+    Display,
+    /// Opens the `<dialog>` with `showModal()` before the enter transition
+    /// and closes it with `close()` after the leave transition.
+    Dialog,
+    /// Shows the `[popover]` with `showPopover()` before the enter
+    /// transition and hides it with `hidePopover()` after the leave
+    /// transition.
+    Popover,
+}
+
+impl HideStrategy {
+    pub(crate) fn set_visible(&self, el: &Element, visible: bool) {
+        match self {
+            Self::Display => {
+                if let Some(style) = element_style(el) {
+                    let _ = style.set_property("display", if visible { "" } else { "none" });
+                }
+            }
+            Self::Dialog => {
+                let Some(dialog) = el.dyn_ref::<HtmlDialogElement>() else {
+                    return;
+                };
+                if visible && !dialog.open() {
+                    let _ = dialog.show_modal();
+                } else if !visible && dialog.open() {
+                    dialog.close();
+                }
+            }
+            Self::Popover => {
+                let Some(el) = el.dyn_ref::<HtmlElement>() else {
+                    return;
+                };
+                let open = el.matches(":popover-open").unwrap_or_default();
+                if visible && !open {
+                    let _ = el.show_popover();
+                } else if !visible && open {
+                    let _ = el.hide_popover();
+                }
+            }
+        }
+    }
+
+    /// Calls `cb` when the browser closes the element on its own, e.g. on
+    /// Escape or a click outside a popover.
+    ///
+    /// Escape on a dialog is prevented, so that it stays open until the
+    /// leave transition has ended. A light-dismissed popover and a dialog
+    /// closed by a form are already closed when `cb` is called, as `toggle`
+    /// and `close` can't be prevented.
+    pub(crate) fn on_dismiss(
+        &self,
+        el: &Element,
+        cb: impl Fn() + Clone + 'static,
+    ) -> Vec<EventListenerHandle> {
+        match self {
+            Self::Display => vec![],
+            Self::Dialog => {
+                let dialog = el.clone();
+                vec![
+                    add_event_listener_untyped(el.clone(), "cancel", {
+                        let cb = cb.clone();
+                        move |ev| {
+                            ev.prevent_default();
+                            cb();
+                        }
+                    }),
+                    // e.g. closed by a `<form method="dialog">`.
+                    add_event_listener_untyped(el.clone(), "close", move |_| {
+                        if !dialog.has_attribute("open") {
+                            cb();
+                        }
+                    }),
+                ]
+            }
+            Self::Popover => {
+                let popover = el.clone();
+                vec![add_event_listener_untyped(
+                    el.clone(),
+                    "toggle",
+                    move |_| {
+                        if !popover.matches(":popover-open").unwrap_or_default() {
+                            cb();
+                        }
+                    },
+                )]
+            }
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{
+    dom_backend::{DomHandle, ElementBackend},
+    scheduler::Scheduler,
+    transition_machine::{wait_for_end, TransitionBackend},
+    utils::Rect,
+};
+use leptos::{html::ElementType, prelude::*, tachys::html::node_ref::node_ref};
+use send_wrapper::SendWrapper;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use web_sys::{
+    js_sys::Array,
+    wasm_bindgen::{closure::Closure, JsCast},
+    HtmlElement, MutationObserver, MutationObserverInit, ResizeObserver,
+};
+
+/// # LayoutTransition
+///
+/// Animates the changes of position and size of its content, e.g. a card
+/// growing because its content changed, or moving because a sibling
+/// appeared. See [`use_layout_transition`].
+///
+/// The content must have a single root HTML element.
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let expanded = RwSignal::new(false);
+/// view! {
+///     <LayoutTransition name="card">
+///         <div class="card" on:click=move |_| expanded.update(|v| *v = !*v)>
+///             <Show when=move || expanded.get()>
+///                 <p>"More details"</p>
+///             </Show>
+///         </div>
+///     </LayoutTransition>
+/// }
+/// # }
+/// ```
+///
+/// Add the following CSS:
+///
+/// ``` css
+/// .card-move {
+///     transition: transform 0.3s ease;
+/// }
+/// ```
+#[component]
+pub fn LayoutTransition<T>(
+    /// Used to automatically generate the `{name}-move` class.
+    #[prop(into)]
+    name: Signal<String>,
+    children: TypedChildren<T>,
+) -> impl IntoView
+where
+    T: AddAnyAttr + IntoView + Send + 'static,
+{
+    let target_ref = NodeRef::<crate::utils::Element>::new();
+    use_layout_transition(target_ref, name);
+
+    children.into_inner()()
+        .into_inner()
+        .add_any_attr(node_ref(target_ref))
+}
+
+/// Plays a FLIP animation when the position or size of the element of
+/// `node_ref` changes.
+///
+/// The layout of the element is measured whenever it is resized or the
+/// children of its parent change. When it differs from the previous one,
+/// the element is transformed back to where it was, then the `{name}-move`
+/// class is added and the transform removed, so that the transition of
+/// `transform` in the class plays the change. While the element is scaled,
+/// its child elements are scaled inversely in every frame, so that their
+/// content isn't distorted.
+pub fn use_layout_transition<E>(node_ref: NodeRef<E>, name: impl Into<Signal<String>>)
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let name = name.into();
+    let scheduler = Scheduler::current();
+
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let Ok(el) = el
+            .unchecked_into::<web_sys::Element>()
+            .dyn_into::<HtmlElement>()
+        else {
+            return;
+        };
+
+        let layout = Rc::new(Layout {
+            last: Cell::new(Rect::measure(&el)),
+            el: el.clone(),
+            name,
+            backend: ElementBackend {
+                el: el.clone().into(),
+                scheduler: scheduler.clone(),
+                on_end: Default::default(),
+            },
+            running: Default::default(),
+            generation: Cell::new(0),
+        });
+
+        let mutation_callback = Closure::<dyn FnMut(Array, MutationObserver)>::new({
+            let layout = layout.clone();
+            move |_, _| layout.on_change()
+        });
+        let mutation_observer = el.parent_node().and_then(|parent| {
+            let observer =
+                MutationObserver::new(mutation_callback.as_ref().unchecked_ref()).ok()?;
+            let options = MutationObserverInit::new();
+            options.set_child_list(true);
+            options.set_subtree(true);
+            options.set_character_data(true);
+            observer.observe_with_options(&parent, &options).ok()?;
+            Some(observer)
+        });
+
+        let resize_callback = Closure::<dyn FnMut(Array, ResizeObserver)>::new({
+            let layout = layout.clone();
+            move |_, _| layout.on_change()
+        });
+        let resize_observer = ResizeObserver::new(resize_callback.as_ref().unchecked_ref()).ok();
+        if let Some(observer) = &resize_observer {
+            observer.observe(&el);
+        }
+
+        let observers = SendWrapper::new((
+            layout,
+            mutation_observer,
+            mutation_callback,
+            resize_observer,
+            resize_callback,
+        ));
+        on_cleanup(move || {
+            let (layout, mutation_observer, _, resize_observer, _) = observers.take();
+            if let Some(observer) = mutation_observer {
+                observer.disconnect();
+            }
+            if let Some(observer) = resize_observer {
+                observer.disconnect();
+            }
+            layout.finish();
+        });
+    });
+}
+
+impl Rect {
+    /// Where the element is drawn with a `transform` of `scale(sx, sy)`
+    /// followed by `translate(x, y)`, with its origin at the top left.
+    fn transformed(&self, [sx, sy, x, y]: [f64; 4]) -> Self {
+        Self {
+            left: self.left + x,
+            top: self.top + y,
+            width: self.width * sx,
+            height: self.height * sy,
+        }
+    }
+}
+
+struct Layout {
+    el: HtmlElement,
+    name: Signal<String>,
+    /// Reads the transition and waits for its end.
+    backend: ElementBackend,
+    /// The layout the element is animated to.
+    last: Cell<Rect>,
+    running: RefCell<Option<Running>>,
+    generation: Cell<u64>,
+}
+
+struct Running {
+    class: String,
+    /// The inline styles of the element before the animation.
+    saved: SavedStyle,
+    /// The child elements scaled inversely, with their inline styles
+    /// before the animation.
+    children: Vec<(HtmlElement, SavedStyle)>,
+    handles: Vec<DomHandle>,
+}
+
+impl Layout {
+    fn on_change(self: &Rc<Self>) {
+        let last = Rect::measure(&self.el);
+        let prev = self.last.replace(last);
+        // Starts from where the element is currently drawn.
+        let first = if self.running.borrow().is_some() {
+            prev.transformed(current_transform(&self.el))
+        } else {
+            prev
+        };
+        self.finish();
+        // Elements appearing or disappearing have nothing to animate from.
+        let empty = |rect: Rect| rect.width == 0.0 || rect.height == 0.0;
+        if first == last || empty(first) || empty(last) {
+            return;
+        }
+
+        let sx = first.width / last.width;
+        let sy = first.height / last.height;
+        let saved = SavedStyle::save(&self.el, &["transform-origin", "transform", "transition"]);
+        let style = self.el.style();
+        let _ = style.set_property("transform-origin", "0 0");
+        let _ = style.set_property(
+            "transform",
+            &format!(
+                "translate({}px, {}px) scale({sx}, {sy})",
+                first.left - last.left,
+                first.top - last.top
+            ),
+        );
+        let _ = style.set_property("transition", "none");
+        let children = if sx != 1.0 || sy != 1.0 {
+            let children = self.children();
+            correct_children(&children, [sx, sy]);
+            children
+        } else {
+            vec![]
+        };
+
+        // Renders the inverted layout before the transition starts.
+        let _ = self.el.offset_height();
+        let class = format!("{}-move", self.name.get_untracked());
+        let _ = self.el.class_list().add_1(&class);
+        saved.restore(&self.el, &["transition", "transform"]);
+
+        let generation = self.generation.get() + 1;
+        self.generation.set(generation);
+        let scaled = !children.is_empty();
+        *self.running.borrow_mut() = Some(Running {
+            class,
+            saved,
+            children,
+            handles: vec![],
+        });
+
+        let Some(info) = self.backend.transition_info() else {
+            self.finish();
+            return;
+        };
+        let handles = wait_for_end(&self.backend, info, {
+            let layout = Rc::downgrade(self);
+            move |_| {
+                if let Some(layout) = layout.upgrade() {
+                    if layout.generation.get() == generation {
+                        layout.finish();
+                    }
+                }
+            }
+        });
+        if let Some(running) = self.running.borrow_mut().as_mut() {
+            running.handles.extend(handles);
+        }
+
+        if scaled {
+            self.request_correction(generation);
+        }
+    }
+
+    /// The child elements, with their inline styles saved.
+    fn children(&self) -> Vec<(HtmlElement, SavedStyle)> {
+        let children = self.el.children();
+        (0..children.length())
+            .filter_map(|i| children.item(i))
+            .filter_map(|child| child.dyn_into::<HtmlElement>().ok())
+            .map(|child| {
+                let saved = SavedStyle::save(&child, &["transform-origin", "transform"]);
+                (child, saved)
+            })
+            .collect()
+    }
+
+    /// Scales the child elements inversely to the current scale of the
+    /// element, in every frame until the transition ends.
+    fn request_correction(self: &Rc<Self>, generation: u64) {
+        let layout = Rc::downgrade(self);
+        self.backend.scheduler.request_frame(move || {
+            let Some(layout) = layout.upgrade() else {
+                return;
+            };
+            if layout.generation.get() != generation {
+                return;
+            }
+            let [sx, sy, ..] = current_transform(&layout.el);
+            match layout.running.borrow().as_ref() {
+                Some(running) => correct_children(&running.children, [sx, sy]),
+                None => return,
+            }
+            layout.request_correction(generation);
+        });
+    }
+
+    /// Ends the running animation, if any.
+    fn finish(&self) {
+        let Some(running) = self.running.borrow_mut().take() else {
+            return;
+        };
+        for handle in running.handles {
+            handle.cancel();
+        }
+        let _ = self.el.class_list().remove_1(&running.class);
+        running.saved.restore(&self.el, &[]);
+        for (child, saved) in &running.children {
+            saved.restore(child, &[]);
+        }
+    }
+}
+
+fn correct_children(children: &[(HtmlElement, SavedStyle)], [sx, sy]: [f64; 2]) {
+    for (child, _) in children {
+        let style = child.style();
+        let _ = style.set_property("transform-origin", "0 0");
+        let _ = style.set_property("transform", &format!("scale({}, {})", 1.0 / sx, 1.0 / sy));
+    }
+}
+
+/// Inline style values overridden during the animation, as `(property,
+/// value, priority)`.
+struct SavedStyle(Vec<(&'static str, String, String)>);
+
+impl SavedStyle {
+    fn save(el: &HtmlElement, properties: &[&'static str]) -> Self {
+        let style = el.style();
+        Self(
+            properties
+                .iter()
+                .map(|property| {
+                    (
+                        *property,
+                        style.get_property_value(property).unwrap_or_default(),
+                        style.get_property_priority(property),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Restores the saved values of `properties`, or of every saved
+    /// property if empty.
+    fn restore(&self, el: &HtmlElement, properties: &[&str]) {
+        let style = el.style();
+        for (property, value, priority) in &self.0 {
+            if !properties.is_empty() && !properties.contains(property) {
+                continue;
+            }
+            if value.is_empty() {
+                let _ = style.remove_property(property);
+            } else {
+                let _ = style.set_property_with_priority(property, value, priority);
+            }
+        }
+    }
+}
+
+/// The scale and translation of the computed `transform` of the element,
+/// as `[sx, sy, x, y]`.
+fn current_transform(el: &HtmlElement) -> [f64; 4] {
+    let transform = window()
+        .get_computed_style(el)
+        .ok()
+        .flatten()
+        .and_then(|styles| styles.get_property_value("transform").ok())
+        .unwrap_or_default();
+    let values = transform
+        .strip_prefix("matrix(")
+        .and_then(|matrix| matrix.strip_suffix(')'))
+        .map(|matrix| {
+            matrix
+                .split(", ")
+                .filter_map(|value| value.parse::<f64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    match values[..] {
+        [a, _, _, d, e, f] => [a, d, e, f],
+        _ => [1.0, 1.0, 0.0, 0.0],
+    }
+}
@@ -1,18 +1,70 @@
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "router")]
+mod animated_outlet;
+mod auto_animate;
+mod dom_backend;
+mod exit_ghost;
 mod get_transition_info;
-mod use_next_frame;
+mod hide_strategy;
+mod layout_transition;
+mod option_transition;
+mod play_transition;
+mod scheduler;
+mod show_transition;
+mod suspense_transition;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod time_scale;
+mod transition_a11y;
+mod transition_controller;
+mod transition_event;
+mod transition_group;
+mod transition_hooks;
+mod transition_info_cache;
+mod transition_machine;
+mod transition_mode;
 mod utils;
+mod view_transition;
+
+#[cfg(feature = "router")]
+pub use animated_outlet::AnimatedOutlet;
+pub use auto_animate::{use_auto_animate, use_auto_animate_with_options, AutoAnimateOptions};
+pub use exit_ghost::use_exit_ghost;
+pub use get_transition_info::{AnimationTypes, CSSTransitionInfo};
+pub use hide_strategy::HideStrategy;
+pub use layout_transition::{use_layout_transition, LayoutTransition};
+pub use option_transition::OptionTransition;
+pub use show_transition::ShowTransition;
+pub use suspense_transition::SuspenseTransition;
+pub use time_scale::{set_time_scale, time_scale};
+pub use transition_a11y::TransitionA11y;
+pub use transition_controller::{TransitionController, TransitionOutcome, TransitionStatus};
+pub use transition_event::{TransitionEvent, TransitionPhase};
+pub use transition_group::{Stagger, StaggerOrder, TransitionGroup};
+pub use transition_hooks::{ArcTransitionHooks, TransitionHooks};
+pub use transition_info_cache::clear_transition_info_cache;
+pub use transition_machine::{
+    StartStrategy, TransitionBackend, TransitionContext, TransitionHook, TransitionMachine,
+    TransitionStage,
+};
+pub use transition_mode::TransitionMode;
+pub use view_transition::{is_view_transition_supported, start_view_transition, ViewTransition};
 
-use get_transition_info::{get_transition_info, AnimationTypes, CSSTransitionInfo};
+use dom_backend::{DomBackend, StageStyles};
 use leptos::{
-    ev,
     prelude::*,
     tachys::html::{node_ref::node_ref, style::style},
+    task::spawn_local,
 };
+use scheduler::Scheduler;
 use send_wrapper::SendWrapper;
-use std::{ops::Deref, time::Duration};
-use use_next_frame::NextFrame;
-use utils::{add_event_listener, ArcOneCallback, EventListenerHandle};
-use web_sys::HtmlElement;
+use std::{sync::Arc, time::Duration};
+use transition_hooks::CallbackHooks;
+use utils::{element_style, ArcOneCallback};
+use view_transition::start_view_transition_sync;
+use web_sys::Element;
 
 /// # CSSTransition
 ///
@@ -25,12 +77,19 @@ use web_sys::HtmlElement;
 /// slot content. If the content is a component, the component must
 /// also have only one single root element.
 ///
+/// The element can be an HTML, SVG or MathML element, e.g. a `<g>` of a
+/// chart or a `<path>` drawn with `stroke-dashoffset`.
+/// The `on_*` callbacks therefore receive a `web_sys::Element`, which
+/// can be cast with `dyn_ref::<HtmlElement>()` where needed.
+///
 /// ## Examples
 ///
 /// This is an example of the most basic usage:
 ///
 /// ``` rust
 /// use leptos::prelude::*;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
 /// let show = RwSignal::new(false);
 /// view!{
 ///     <button on:click=move |_| show.update(|v| *v = !*v)>"Toggle"</button>
@@ -41,6 +100,7 @@ use web_sys::HtmlElement;
 ///         <p>"hello"</p>
 ///     </CSSTransition>
 /// }
+/// # }
 /// ```
 ///
 /// Add the following CSS:
@@ -50,7 +110,7 @@ use web_sys::HtmlElement;
 /// .fade-leave-active {
 ///     transition: opacity 0.5s ease;
 /// }
-
+///
 /// .fade-enter-from,
 /// .fade-leave-to {
 ///     opacity: 0;
@@ -67,228 +127,330 @@ pub fn CSSTransition<T>(
     /// Whether to apply transition on initial render.
     #[prop(optional)]
     appear: bool,
-    #[prop(optional, into)] on_before_enter: Option<ArcOneCallback<HtmlElement>>,
-    #[prop(optional, into)] on_enter: Option<ArcOneCallback<HtmlElement>>,
-    #[prop(optional, into)] on_after_enter: Option<ArcOneCallback<HtmlElement>>,
-    #[prop(optional, into)] on_before_leave: Option<ArcOneCallback<HtmlElement>>,
-    #[prop(optional, into)] on_leave: Option<ArcOneCallback<HtmlElement>>,
-    #[prop(optional, into)] on_after_leave: Option<ArcOneCallback<HtmlElement>>,
+    #[prop(optional, into)] on_before_enter: Option<ArcOneCallback<Element>>,
+    #[prop(optional, into)] on_enter: Option<ArcOneCallback<Element>>,
+    #[prop(optional, into)] on_after_enter: Option<ArcOneCallback<Element>>,
+    #[prop(optional, into)] on_before_leave: Option<ArcOneCallback<Element>>,
+    #[prop(optional, into)] on_leave: Option<ArcOneCallback<Element>>,
+    #[prop(optional, into)] on_after_leave: Option<ArcOneCallback<Element>>,
+    /// Lifecycle hooks, called after the `on_*` callbacks.
+    #[prop(optional, into)]
+    hooks: Option<ArcTransitionHooks>,
+    /// Delay before the `*-from` classes are swapped for the `*-to` classes.
+    /// The end of the transition is awaited after the delay has elapsed.
+    #[prop(optional, into)]
+    delay: Option<Signal<Duration>>,
+    /// Imperative handle used to drive the transition and await its end.
+    #[prop(optional)]
+    controller: Option<TransitionController>,
+    /// Uses the View Transitions API instead of the transition classes.
+    /// Browsers without support fall back to the transition classes.
+    #[prop(optional)]
+    view_transition: bool,
+    /// The `view-transition-name` of the element.
+    #[prop(optional, into)]
+    view_transition_name: Option<Signal<String>>,
+    /// Reuses the durations computed for an element with the same tag and
+    /// classes instead of reading the computed style again. Not suited to
+    /// durations that depend on inline styles, media queries or ancestors:
+    /// the cache is only cleared when a stylesheet in `<head>` changes, see
+    /// [`clear_transition_info_cache`].
+    #[prop(optional)]
+    cache_info: bool,
+    /// How the `*-from` classes get rendered before the `*-to` classes are
+    /// applied.
+    #[prop(optional)]
+    start_strategy: StartStrategy,
+    /// CSS custom properties set on the element while it is entering or
+    /// leaving, e.g. `vec![("--distance".into(), "20px".into())]`. They are
+    /// set before the durations are computed, and removed afterwards.
+    #[prop(optional, into)]
+    vars: Option<Signal<Vec<(String, String)>>>,
+    /// Inline styles applied along with the `*-enter-from` class, e.g.
+    /// `vec![("opacity".into(), "0".into())]`. With the other stage styles,
+    /// transitions work without any stylesheet. They override the inline
+    /// styles of the element for the same properties.
+    #[prop(optional)]
+    enter_from_style: Vec<(String, String)>,
+    /// Inline styles applied along with the `*-enter-active` class.
+    #[prop(optional)]
+    enter_active_style: Vec<(String, String)>,
+    /// Inline styles applied along with the `*-enter-to` class.
+    #[prop(optional)]
+    enter_to_style: Vec<(String, String)>,
+    /// Inline styles applied along with the `*-leave-from` class.
+    #[prop(optional)]
+    leave_from_style: Vec<(String, String)>,
+    /// Inline styles applied along with the `*-leave-active` class.
+    #[prop(optional)]
+    leave_active_style: Vec<(String, String)>,
+    /// Inline styles applied along with the `*-leave-to` class.
+    #[prop(optional)]
+    leave_to_style: Vec<(String, String)>,
+    /// How the element is shown and hidden. Use [`HideStrategy::Dialog`]
+    /// for a `<dialog>` and [`HideStrategy::Popover`] for a `[popover]`,
+    /// which live in the top layer instead of being hidden with `display`.
+    #[prop(optional)]
+    hide_strategy: HideStrategy,
+    /// Called when the browser closes the dialog or popover on its own,
+    /// e.g. on Escape. `show` should be set to `false` in response, which
+    /// the `controller` does by itself. A popover closed by a click outside
+    /// or a dialog closed by a `<form method="dialog">` is reopened until
+    /// its leave transition has ended, so it stays open if `show` stays
+    /// `true`.
+    #[prop(optional, into)]
+    on_dismiss: Option<ArcOneCallback<Element>>,
+    /// Focus management and hiding from assistive technologies, e.g.
+    /// `TransitionA11y::new().inert().restore_focus()`.
+    #[prop(optional)]
+    a11y: TransitionA11y,
     children: TypedChildren<T>,
 ) -> impl IntoView
 where
     T: AddAnyAttr + IntoView + Send + 'static,
 {
-    let target_ref = NodeRef::<utils::HtmlElement>::new();
-    let next_frame = NextFrame::new();
-    let end_handle = StoredValue::new(None::<EventListenerHandle>);
-    let end_count = StoredValue::new(None::<usize>);
-    let finish = StoredValue::new(None::<Box<dyn FnOnce() + Send + Sync>>);
+    let hooks: Arc<dyn TransitionHooks> = Arc::new((
+        CallbackHooks {
+            on_before_enter,
+            on_enter,
+            on_after_enter,
+            on_before_leave,
+            on_leave,
+            on_after_leave,
+        },
+        hooks.map(|hooks| Arc::clone(&hooks)),
+    ));
+    let stage_styles = Arc::new(StageStyles {
+        enter_from: enter_from_style,
+        enter_active: enter_active_style,
+        enter_to: enter_to_style,
+        leave_from: leave_from_style,
+        leave_active: leave_active_style,
+        leave_to: leave_to_style,
+    });
+    let target_ref = NodeRef::<utils::Element>::new();
+    let scheduler = Scheduler::current();
+    // The running view transition.
+    let finish = StoredValue::new(None::<Box<dyn FnOnce(bool) + Send + Sync>>);
+    let running = StoredValue::new(false);
+    let view_transition_id = StoredValue::new(0usize);
+
+    let show = match controller {
+        Some(controller) => controller.bind(show),
+        None => show,
+    };
+    let settle = move |show: bool, outcome: TransitionOutcome| {
+        if let Some(controller) = controller {
+            controller.settle(show, outcome);
+        }
+    };
+    let set_status = move |status: TransitionStatus| {
+        if let Some(controller) = controller {
+            controller.set_status(status);
+        }
+    };
 
     Effect::new(move |_| {
         let Some(el) = target_ref.get() else {
             return;
         };
 
-        let class_list = el.class_list();
-        let style = el.style();
-
-        let on_end = {
-            let el = send_wrapper::SendWrapper::new(el.clone());
-            move |remove: Box<dyn FnOnce() + Send + Sync>| {
-                let Some(CSSTransitionInfo {
-                    types,
-                    prop_count,
-                    timeout,
-                }) = get_transition_info(&el)
-                else {
-                    remove();
-                    return;
-                };
-
-                finish.set_value(Some(Box::new(move || {
-                    end_count.set_value(None);
-                    remove();
-                    end_handle.update_value(|h| {
-                        h.take().map(|h| {
-                            h.remove();
-                        });
-                    });
-                })));
-
-                set_timeout(
-                    move || {
-                        if let Some(Some(f)) = finish.try_update_value(|f| f.take()) {
-                            f();
-                        }
-                    },
-                    Duration::from_millis(timeout + 1),
-                );
+        if element_style(&el).is_none() {
+            return;
+        }
+        let machine = TransitionMachine::new(DomBackend {
+            el: el.clone(),
+            scheduler: scheduler.clone(),
+            hooks: hooks.clone(),
+            controller,
+            cache_info,
+            vars,
+            applied_vars: Default::default(),
+            stage_styles: stage_styles.clone(),
+            hide_strategy,
+            a11y: a11y.clone(),
+            focus_origin: Default::default(),
+        });
+        machine.set_start_strategy(start_strategy);
 
-                end_count.set_value(Some(0));
-                let event_listener = move || {
-                    end_count.update_value(|v| {
-                        let Some(v) = v else {
-                            return;
-                        };
-                        *v += 1;
-                    });
-                    if end_count.with_value(|v| {
-                        let Some(v) = v else {
-                            return false;
-                        };
-                        *v >= prop_count
-                    }) {
-                        if let Some(Some(f)) = finish.try_update_value(|f| f.take()) {
-                            f();
-                        }
-                    }
-                };
-                let handle = match types {
-                    AnimationTypes::Transition => {
-                        add_event_listener(el.deref().clone(), ev::transitionend, move |_| {
-                            event_listener()
-                        })
-                    }
-                    AnimationTypes::Animation => {
-                        add_event_listener(el.deref().clone(), ev::animationend, move |_| {
-                            event_listener()
-                        })
-                    }
-                };
-                end_handle.set_value(Some(handle));
-            }
-        };
+        // Follows the changes of `vars` during a transition.
+        let vars_effect = vars.map(|vars| {
+            let machine = machine.clone();
+            RenderEffect::new(move |_| {
+                vars.track();
+                if machine.is_running() {
+                    machine.backend().apply_vars();
+                }
+            })
+        });
 
-        let on_finish = move || {
+        // `cancelled` is true when the running view transition is
+        // interrupted by the opposite one. Returns whether one was running.
+        let on_finish = move |cancelled: bool| {
             if let Some(Some(f)) = finish.try_update_value(|f| f.take()) {
-                f();
+                f(cancelled);
+                true
+            } else {
+                false
             }
         };
 
-        let name = name.clone();
-        let on_before_enter = on_before_enter.clone();
-        let on_enter = on_enter.clone();
-        let on_after_enter = on_after_enter.clone();
-        let on_before_leave = on_before_leave.clone();
-        let on_leave = on_leave.clone();
-        let on_after_leave = on_after_leave.clone();
-        let effect = RenderEffect::new(move |prev: Option<bool>| {
-            let show = show.get();
-            let prev = if let Some(prev) = prev {
-                prev
-            } else if show && appear {
-                false
-            } else {
-                if show {
-                    let _ = style.set_property("display", "");
+        if let Some(controller) = controller {
+            let machine = SendWrapper::new(machine.clone());
+            controller.attach(move |cancelled| {
+                on_finish(cancelled);
+                if cancelled {
+                    machine.cancel();
                 } else {
-                    let _ = style.set_property("display", "none");
+                    machine.complete();
                 }
-                return show;
-            };
+            });
+        }
 
-            let name = name.get_untracked();
+        let effect = RenderEffect::new({
+            let machine = machine.clone();
+            let el = el.clone();
+            move |prev: Option<bool>| {
+                let show = show.get();
+                let is_appear = prev.is_none();
+                let prev = if let Some(prev) = prev {
+                    prev
+                } else if show && appear {
+                    false
+                } else {
+                    machine.backend().set_visible(show);
+                    machine.backend().a11y.set_hidden(&el, !show);
+                    set_status(if show {
+                        TransitionStatus::Entered
+                    } else {
+                        TransitionStatus::Left
+                    });
+                    settle(show, TransitionOutcome::Finished);
+                    return show;
+                };
 
-            if show && !prev {
-                on_finish();
-                {
-                    // on_enter
-                    if let Some(on_before_enter) = on_before_enter.as_ref() {
-                        on_before_enter(el.clone());
-                    }
+                let name = name.get_untracked();
+                let delay = delay.map(|delay| delay.get_untracked()).unwrap_or_default();
 
-                    let enter_from = format!("{name}-enter-from");
-                    let enter_active = format!("{name}-enter-active");
-                    let enter_to = format!("{name}-enter-to");
+                // Futures waiting for the opposite state will never finish.
+                settle(!show, TransitionOutcome::Cancelled);
 
-                    // enter-from enter-active
-                    let _ = class_list.add_2(&enter_from, &enter_active);
-                    let _ = style.set_property("display", "");
+                // `TransitionController::cancel` shows the content where a
+                // leave was cancelled, `show` only catches up with it.
+                let resynced = show
+                    && !prev
+                    && !running.get_value()
+                    && !machine.is_running()
+                    && controller.is_some_and(|controller| {
+                        controller.status_untracked() == TransitionStatus::Entered
+                    });
 
-                    let class_list = class_list.clone();
-                    let on_end = on_end.clone();
-                    let on_enter = on_enter.clone();
-                    let on_after_enter = on_after_enter.clone();
-                    let el = el.clone();
-                    next_frame.run(move || {
-                        // enter-active enter-to
-                        let _ = class_list.remove_1(&enter_from);
-                        let _ = class_list.add_1(&enter_to);
+                if resynced {
+                    settle(show, TransitionOutcome::Finished);
+                } else if show != prev && view_transition && is_view_transition_supported() {
+                    let interrupted = on_finish(true);
+                    running.set_value(true);
 
-                        let remove = {
-                            let class_list = SendWrapper::new(class_list);
-                            let el = SendWrapper::new(el.clone());
-                            Box::new(move || {
-                                let _ = class_list.remove_2(&enter_active, &enter_to);
-                                if let Some(on_after_enter) = on_after_enter.as_ref() {
-                                    on_after_enter(el.deref().clone());
-                                }
-                            })
-                        };
-                        on_end(remove);
+                    // Reported like the transitions of the machine, so that the
+                    // a11y options, `vars` and the controller apply too.
+                    let context = TransitionContext {
+                        phase: if show {
+                            TransitionPhase::Enter
+                        } else {
+                            TransitionPhase::Leave
+                        },
+                        appear: is_appear,
+                        interrupted,
+                        info: None,
+                    };
+                    let notify = {
+                        let machine = SendWrapper::new(machine.clone());
+                        move |hook: TransitionHook| machine.backend().notify(hook, &context)
+                    };
+                    notify(if show {
+                        TransitionHook::BeforeEnter
+                    } else {
+                        TransitionHook::BeforeLeave
+                    });
 
-                        if let Some(on_enter) = on_enter.as_ref() {
-                            on_enter(el);
+                    let transition = start_view_transition_sync({
+                        let el = el.clone();
+                        let notify = notify.clone();
+                        move || {
+                            hide_strategy.set_visible(&el, show);
+                            notify(if show {
+                                TransitionHook::Enter
+                            } else {
+                                TransitionHook::Leave
+                            });
                         }
                     });
-                }
-            } else if !show && prev {
-                on_finish();
-                {
-                    // on_leave
-                    if let Some(on_before_leave) = on_before_leave.as_ref() {
-                        on_before_leave(el.clone());
-                    }
 
-                    let leave_from = format!("{name}-leave-from");
-                    let leave_active = format!("{name}-leave-active");
-                    let leave_to = format!("{name}-leave-to");
-
-                    // leave-from leave-active
-                    let _ = class_list.add_2(&leave_from, &leave_active);
-
-                    let class_list = class_list.clone();
-                    let style = style.clone();
-                    let on_end = on_end.clone();
-                    let on_leave = on_leave.clone();
-                    let on_after_leave = on_after_leave.clone();
-                    let el = el.clone();
-                    next_frame.run(move || {
-                        // leave-active leave-to
-                        let _ = class_list.remove_1(&leave_from);
-                        let _ = class_list.add_1(&leave_to);
-
-                        let remove = {
-                            let class_list = SendWrapper::new(class_list);
-                            let style = SendWrapper::new(style);
-                            let el = SendWrapper::new(el.clone());
-                            Box::new(move || {
-                                let _ = class_list.remove_2(&leave_active, &leave_to);
-                                let _ = style.set_property("display", "none");
-                                if let Some(on_after_leave) = on_after_leave.as_ref() {
-                                    on_after_leave(el.deref().clone());
-                                }
-                            })
-                        };
-                        on_end(remove);
+                    finish.set_value(Some(Box::new(move |cancelled: bool| {
+                        running.set_value(false);
+                        notify(match (show, cancelled) {
+                            (true, false) => TransitionHook::AfterEnter,
+                            (true, true) => TransitionHook::EnterCancelled,
+                            (false, false) => TransitionHook::AfterLeave,
+                            (false, true) => TransitionHook::LeaveCancelled,
+                        });
+                    })));
 
-                        if let Some(on_leave) = on_leave {
-                            on_leave(el);
+                    // `finished` also resolves when the transition is skipped
+                    // by a newer one, which has already taken care of `finish`.
+                    let id = view_transition_id.get_value() + 1;
+                    view_transition_id.set_value(id);
+                    spawn_local(async move {
+                        let _ = transition.finished().await;
+                        if view_transition_id.try_get_value() == Some(id) {
+                            on_finish(false);
                         }
                     });
+                } else if show && !prev {
+                    machine.enter(&name, delay, is_appear);
+                } else if !show && prev {
+                    machine.leave(&name, delay);
+                } else if !running.get_value() && !machine.is_running() {
+                    settle(show, TransitionOutcome::Finished);
                 }
+
+                show
             }
+        });
 
-            show
+        let dismiss_listeners = hide_strategy.on_dismiss(&el, {
+            let el = el.clone();
+            let on_dismiss = on_dismiss.clone();
+            move || {
+                if !show.get_untracked() {
+                    return;
+                }
+                // A light-dismissed popover or a dialog closed by a form has
+                // already left the top layer, it's reopened to play the leave
+                // transition.
+                hide_strategy.set_visible(&el, true);
+                if let Some(on_dismiss) = &on_dismiss {
+                    on_dismiss(el.clone());
+                }
+                if let Some(controller) = controller {
+                    controller.set_show(false);
+                }
+            }
         });
 
+        let machine = SendWrapper::new(machine);
+        let dismiss_listeners = SendWrapper::new(dismiss_listeners);
         on_cleanup(move || {
             drop(effect);
-            end_handle.update_value(|handle| {
-                if let Some(handle) = handle.take() {
-                    handle.remove();
-                }
-            });
+            drop(vars_effect);
+            for listener in dismiss_listeners.take() {
+                listener.remove();
+            }
+            machine.stop();
+            if let Some(controller) = controller {
+                controller.detach();
+                controller.cancel_all();
+            }
         })
     });
 
@@ -296,7 +458,14 @@ where
         .into_inner()
         .add_any_attr(style((
             "display",
-            if show.get_untracked() { "" } else { "none" },
+            if show.get_untracked() || hide_strategy != HideStrategy::Display {
+                ""
+            } else {
+                "none"
+            },
         )))
+        .add_any_attr(style(("view-transition-name", move || {
+            view_transition_name.map(|name| name.get())
+        })))
         .add_any_attr(node_ref(target_ref))
 }
@@ -0,0 +1,121 @@
+use crate::CSSTransition;
+use leptos::prelude::*;
+
+/// # OptionTransition
+///
+/// Shows its content while `value` is `Some`, with enter and leave
+/// animations. The last `Some` value is retained, so that the content can
+/// still be rendered while it is leaving.
+///
+/// A change from one `Some` value to another re-renders the content
+/// without a transition. The view returned by `children` must have a
+/// single root element.
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let selected = RwSignal::new(Some(String::from("hello")));
+/// view! {
+///     <button on:click=move |_| selected.set(None)>"Close"</button>
+///     <OptionTransition
+///         value=selected
+///         name="fade"
+///         children=|text: String| view! { <p>{text}</p> }
+///     />
+/// }
+/// # }
+/// ```
+#[component]
+pub fn OptionTransition<T, CF, V>(
+    /// The content is shown while this is `Some`.
+    #[prop(into)]
+    value: Signal<Option<T>>,
+    /// Used to automatically generate transition CSS class names.
+    #[prop(into)]
+    name: Signal<String>,
+    /// Whether to apply transition on initial render.
+    #[prop(optional)]
+    appear: bool,
+    children: CF,
+) -> impl IntoView
+where
+    T: Clone + Send + Sync + 'static,
+    CF: Fn(T) -> V + Send + Sync + 'static,
+    V: AddAnyAttr + IntoView + Send + 'static,
+{
+    // The revision whose content has left.
+    let left = RwSignal::new(None::<u64>);
+    // Derived synchronously from `value`, so that the content and whether
+    // it is shown always change together.
+    let state = Memo::new(move |prev: Option<&State<T>>| match value.get() {
+        Some(value) => State {
+            revision: prev.map_or(0, |prev| prev.revision + 1),
+            // Content that is hidden or leaving enters again.
+            content: Some((value, prev.map_or(appear, |prev| !prev.shown))),
+            shown: true,
+        },
+        None => {
+            let Some(prev) = prev else {
+                return State {
+                    revision: 0,
+                    content: None,
+                    shown: false,
+                };
+            };
+            if left.get() == Some(prev.revision) && prev.content.is_some() {
+                State {
+                    revision: prev.revision + 1,
+                    content: None,
+                    shown: false,
+                }
+            } else {
+                State {
+                    revision: prev.revision,
+                    content: prev.content.clone(),
+                    shown: false,
+                }
+            }
+        }
+    });
+    let revision = Memo::new(move |_| state.with(|state| state.revision));
+
+    move || {
+        let revision = revision.get();
+        let content = state.with_untracked(|state| state.content.clone());
+        content.map(|(value, appear)| {
+            // Content replaced by a newer revision is never shown again.
+            let show = Signal::derive(move || {
+                state.with(|state| state.shown && state.revision == revision)
+            });
+            let on_after_leave = move |_: web_sys::Element| {
+                if state.with_untracked(|state| !state.shown && state.revision == revision) {
+                    left.set(Some(revision));
+                }
+            };
+            let child = children(value);
+            view! {
+                <CSSTransition show=show name=name appear=appear on_after_leave=on_after_leave>
+                    {child}
+                </CSSTransition>
+            }
+        })
+    }
+}
+
+/// The rendered content of an [`OptionTransition`].
+struct State<T> {
+    /// Increased whenever the content is replaced.
+    revision: u64,
+    /// The rendered value, and whether it appears with a transition.
+    content: Option<(T, bool)>,
+    shown: bool,
+}
+
+impl<T> PartialEq for State<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.revision == other.revision && self.shown == other.shown
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{
+    dom_backend::ElementBackend, scheduler::Scheduler, transition_machine::TransitionMachine,
+    TransitionPhase,
+};
+use std::time::Duration;
+use web_sys::Element;
+
+/// A transition played by [`play_transition`], `complete` jumps to its end.
+pub(crate) type PlayTransition = TransitionMachine<ElementBackend>;
+
+/// Plays the `{name}-enter-*` or `{name}-leave-*` classes on an element
+/// that is not rendered by a `CSSTransition`, then calls `on_end`.
+pub(crate) fn play_transition(
+    scheduler: &Scheduler,
+    el: Element,
+    name: &str,
+    phase: TransitionPhase,
+    on_end: impl FnOnce() + 'static,
+) -> PlayTransition {
+    let machine = TransitionMachine::new(ElementBackend {
+        el,
+        scheduler: scheduler.clone(),
+        on_end: Default::default(),
+    });
+    // The machine is kept alive until the transition ends, even if the
+    // returned handle is dropped.
+    *machine.backend().on_end.borrow_mut() = Some(Box::new({
+        let machine = machine.clone();
+        move || {
+            drop(machine);
+            on_end();
+        }
+    }));
+
+    match phase {
+        TransitionPhase::Enter => machine.enter(name, Duration::ZERO, false),
+        TransitionPhase::Leave => machine.leave(name, Duration::ZERO),
+    }
+
+    machine
+}
@@ -0,0 +1,184 @@
+use leptos::prelude::{set_timeout_with_handle, window, TimeoutHandle};
+use std::{cell::RefCell, time::Duration};
+use web_sys::wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+
+/// The longest delay `setTimeout` takes, as an `i32` of milliseconds.
+/// Longer delays, e.g. of transitions slowed down by a large time scale,
+/// are clamped to it.
+const MAX_TIMEOUT: Duration = Duration::from_millis(i32::MAX as u64);
+
+/// Where animation frames and timeouts are scheduled.
+///
+/// With the `testing` feature, a [`MockScheduler`](crate::testing::MockScheduler)
+/// provided via context replaces the browser.
+#[derive(Clone)]
+pub(crate) enum Scheduler {
+    Browser,
+    #[cfg(any(test, feature = "testing"))]
+    Mock(crate::testing::MockScheduler),
+}
+
+impl Scheduler {
+    /// The scheduler of the current reactive owner.
+    pub fn current() -> Self {
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(scheduler) = leptos::prelude::use_context::<crate::testing::MockScheduler>() {
+            return Self::Mock(scheduler);
+        }
+
+        Self::Browser
+    }
+
+    /// Runs `cb` in the write phase of the next frame.
+    pub fn request_frame(&self, cb: impl FnOnce() + 'static) -> ScheduleHandle {
+        match self {
+            Self::Browser => ScheduleHandle::Frame(FrameQueue::push(FramePhase::Write, cb)),
+            #[cfg(any(test, feature = "testing"))]
+            Self::Mock(scheduler) => {
+                ScheduleHandle::Mock(scheduler.clone(), scheduler.request_frame(cb))
+            }
+        }
+    }
+
+    /// Runs `cb` in the read phase of the current frame if called while a
+    /// frame is flushed, otherwise in the read phase of the next frame.
+    pub fn request_read(&self, cb: impl FnOnce() + 'static) -> ScheduleHandle {
+        match self {
+            Self::Browser => ScheduleHandle::Frame(FrameQueue::push(FramePhase::Read, cb)),
+            #[cfg(any(test, feature = "testing"))]
+            Self::Mock(scheduler) => {
+                ScheduleHandle::Mock(scheduler.clone(), scheduler.request_read(cb))
+            }
+        }
+    }
+
+    pub fn set_timeout(&self, cb: impl FnOnce() + 'static, delay: Duration) -> ScheduleHandle {
+        match self {
+            Self::Browser => ScheduleHandle::Timeout(
+                set_timeout_with_handle(cb, delay.min(MAX_TIMEOUT))
+                    .expect_throw("set_timeout_with_handle"),
+            ),
+            #[cfg(any(test, feature = "testing"))]
+            Self::Mock(scheduler) => {
+                ScheduleHandle::Mock(scheduler.clone(), scheduler.set_timeout(cb, delay))
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum ScheduleHandle {
+    Frame(u64),
+    Timeout(TimeoutHandle),
+    #[cfg(any(test, feature = "testing"))]
+    Mock(crate::testing::MockScheduler, u64),
+}
+
+impl ScheduleHandle {
+    pub fn cancel(&self) {
+        match self {
+            Self::Frame(id) => FrameQueue::cancel(*id),
+            Self::Timeout(handle) => handle.clear(),
+            #[cfg(any(test, feature = "testing"))]
+            Self::Mock(scheduler, id) => scheduler.cancel(*id),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FramePhase {
+    Write,
+    Read,
+}
+
+thread_local! {
+    static FRAME_QUEUE: RefCell<FrameQueue> = Default::default();
+}
+
+/// The frame callbacks of every transition on the page, flushed by a
+/// single `requestAnimationFrame`.
+///
+/// All writes of a frame run before all reads, so toggling many elements
+/// at once forces style recalculation once instead of once per element.
+#[derive(Default)]
+struct FrameQueue {
+    next_id: u64,
+    requested: bool,
+    // Whether a frame is being flushed.
+    flushing: bool,
+    writes: Vec<(u64, Box<dyn FnOnce()>)>,
+    reads: Vec<(u64, Box<dyn FnOnce()>)>,
+}
+
+impl FrameQueue {
+    fn push(phase: FramePhase, cb: impl FnOnce() + 'static) -> u64 {
+        let (id, request) = FRAME_QUEUE.with_borrow_mut(|queue| {
+            queue.next_id += 1;
+            let id = queue.next_id;
+            match phase {
+                FramePhase::Write => queue.writes.push((id, Box::new(cb))),
+                FramePhase::Read => queue.reads.push((id, Box::new(cb))),
+            }
+            let request = !queue.requested && (phase == FramePhase::Write || !queue.flushing);
+            queue.requested |= request;
+            (id, request)
+        });
+        if request {
+            request_animation_frame(Self::flush);
+        }
+        id
+    }
+
+    fn cancel(id: u64) {
+        FRAME_QUEUE.with_borrow_mut(|queue| {
+            queue.writes.retain(|(write_id, _)| *write_id != id);
+            queue.reads.retain(|(read_id, _)| *read_id != id);
+        });
+    }
+
+    fn flush() {
+        let ids = FRAME_QUEUE.with_borrow_mut(|queue| {
+            queue.requested = false;
+            queue.flushing = true;
+            queue.writes.iter().map(|(id, _)| *id).collect::<Vec<_>>()
+        });
+
+        // Writes requested meanwhile wait for the next frame.
+        for id in ids {
+            let cb = FRAME_QUEUE.with_borrow_mut(|queue| {
+                let index = queue
+                    .writes
+                    .iter()
+                    .position(|(write_id, _)| *write_id == id)?;
+                Some(queue.writes.remove(index).1)
+            });
+            if let Some(cb) = cb {
+                cb();
+            }
+        }
+
+        // Reads requested meanwhile still run in this frame.
+        while let Some(cb) = FRAME_QUEUE
+            .with_borrow_mut(|queue| (!queue.reads.is_empty()).then(|| queue.reads.remove(0).1))
+        {
+            cb();
+        }
+
+        let request = FRAME_QUEUE.with_borrow_mut(|queue| {
+            queue.flushing = false;
+            let request = !queue.requested && !queue.writes.is_empty();
+            queue.requested |= request;
+            request
+        });
+        if request {
+            request_animation_frame(Self::flush);
+        }
+    }
+}
+
+fn request_animation_frame(cb: impl FnOnce() + 'static) {
+    let closure = Closure::once_into_js(cb);
+    window()
+        .request_animation_frame(closure.unchecked_ref())
+        .expect_throw("request_animation_frame");
+}
@@ -0,0 +1,121 @@
+use crate::{CSSTransition, TransitionMode};
+use leptos::prelude::*;
+
+/// # ShowTransition
+///
+/// Like leptos' `<Show>`, renders `children` while `when` is `true` and
+/// `fallback` otherwise, with a transition between both.
+///
+/// Both branches are rendered in a `CSSTransition`, so each must have a
+/// single root element. In `TransitionMode::Simultaneous` both branches are
+/// in the layout at the same time, so the leaving one usually needs
+/// `position: absolute` in its `*-leave-active` class.
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let logged_in = RwSignal::new(false);
+/// view! {
+///     <ShowTransition
+///         when=logged_in
+///         name="fade"
+///         mode=TransitionMode::OutIn
+///         fallback=|| view! { <button>"Log in"</button> }
+///     >
+///         <p>"Welcome"</p>
+///     </ShowTransition>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn ShowTransition<C, F, FV>(
+    /// Whether `children` or `fallback` is shown.
+    #[prop(into)]
+    when: Signal<bool>,
+    /// Used to automatically generate transition CSS class names.
+    #[prop(into)]
+    name: Signal<String>,
+    /// The transition name of the fallback, `name` by default.
+    #[prop(optional, into)]
+    fallback_name: Option<Signal<String>>,
+    /// The order of the leave and enter transitions.
+    #[prop(optional)]
+    mode: TransitionMode,
+    /// Whether to apply transition on initial render.
+    #[prop(optional)]
+    appear: bool,
+    /// Rendered while `when` is `false`.
+    fallback: F,
+    children: TypedChildren<C>,
+) -> impl IntoView
+where
+    C: AddAnyAttr + IntoView + Send + 'static,
+    F: FnOnce() -> FV + Send + 'static,
+    FV: AddAnyAttr + IntoView + Send + 'static,
+{
+    let fallback_name = fallback_name.unwrap_or(name);
+    let show_children = RwSignal::new(when.get_untracked());
+    let show_fallback = RwSignal::new(!when.get_untracked());
+
+    Effect::watch(
+        move || when.get(),
+        move |when, _, _| {
+            let (entering, leaving) = if *when {
+                (show_children, show_fallback)
+            } else {
+                (show_fallback, show_children)
+            };
+            match mode {
+                TransitionMode::Simultaneous => {
+                    leaving.set(false);
+                    entering.set(true);
+                }
+                // The entering branch waits for the `on_after_leave` of
+                // the leaving one.
+                TransitionMode::OutIn => {
+                    if leaving.get_untracked() {
+                        leaving.set(false);
+                    } else {
+                        entering.set(true);
+                    }
+                }
+            }
+        },
+        false,
+    );
+
+    // Enters the other branch once this one has left, if `when` is `next`.
+    let on_after_leave = move |next: bool| {
+        move |_: web_sys::Element| {
+            if mode == TransitionMode::OutIn && when.get_untracked() == next {
+                if next {
+                    show_children.set(true);
+                } else {
+                    show_fallback.set(true);
+                }
+            }
+        }
+    };
+
+    view! {
+        <CSSTransition
+            show=show_children
+            name=name
+            appear=appear
+            on_after_leave=on_after_leave(false)
+        >
+            {children.into_inner()().into_inner()}
+        </CSSTransition>
+        <CSSTransition
+            show=show_fallback
+            name=fallback_name
+            appear=appear
+            on_after_leave=on_after_leave(true)
+        >
+            {fallback()}
+        </CSSTransition>
+    }
+}
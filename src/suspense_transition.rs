@@ -0,0 +1,117 @@
+use crate::{scheduler::Scheduler, ShowTransition, TransitionMode};
+use leptos::{
+    prelude::*,
+    reactive::{computed::suspense::SuspenseContext, owner::Owner},
+};
+use std::time::Duration;
+
+/// # SuspenseTransition
+///
+/// Like leptos' `<Suspense>`, shows `fallback` while the resources read in
+/// `children` are loading, with a transition between the fallback and the
+/// resolved content. Built on [`ShowTransition`], so the fallback must have
+/// a single root element. `children` are rendered in a `<div>`, which the
+/// transition classes are applied to.
+///
+/// The fallback is shown for at least `min_fallback_time`, so that it
+/// doesn't flicker when the resources resolve quickly.
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use std::time::Duration;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let user = Resource::new(|| (), |_| async { String::from("Alice") });
+/// view! {
+///     <SuspenseTransition
+///         name="fade"
+///         mode=TransitionMode::OutIn
+///         min_fallback_time=Duration::from_millis(300)
+///         fallback=|| view! { <div class="skeleton" /> }
+///     >
+///         <p>{move || Suspend::new(async move { user.await })}</p>
+///     </SuspenseTransition>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn SuspenseTransition<C, F, FV>(
+    /// Used to automatically generate transition CSS class names.
+    #[prop(into)]
+    name: Signal<String>,
+    /// The transition name of the fallback, `name` by default.
+    #[prop(optional, into)]
+    fallback_name: Option<Signal<String>>,
+    /// The order of the leave and enter transitions.
+    #[prop(optional)]
+    mode: TransitionMode,
+    /// The minimum time the fallback is shown for.
+    #[prop(optional)]
+    min_fallback_time: Duration,
+    /// Shown while the resources are loading.
+    fallback: F,
+    children: TypedChildren<C>,
+) -> impl IntoView
+where
+    C: IntoView + Send + 'static,
+    F: FnOnce() -> FV + Send + 'static,
+    FV: AddAnyAttr + IntoView + Send + 'static,
+{
+    let scheduler = Scheduler::current();
+    // The owner of the inner `Suspense`, which provides its context. Only
+    // known once it has suspended, so the effect below tracks it.
+    let suspense = RwSignal::new(None::<Owner>);
+    let pending = RwSignal::new(true);
+    let min_elapsed = RwSignal::new(false);
+    let timer_id = StoredValue::new(0u64);
+    let ready = Signal::derive(move || !pending.get() && min_elapsed.get());
+
+    let start_timer = move || {
+        let id = timer_id.get_value() + 1;
+        timer_id.set_value(id);
+        min_elapsed.set(min_fallback_time.is_zero());
+        if !min_fallback_time.is_zero() {
+            scheduler.set_timeout(
+                move || {
+                    if timer_id.try_get_value() == Some(id) {
+                        min_elapsed.set(true);
+                    }
+                },
+                min_fallback_time,
+            );
+        }
+    };
+    start_timer();
+
+    Effect::new(move |_| {
+        let context = suspense
+            .get()
+            .and_then(|owner| owner.with(use_context::<SuspenseContext>));
+        let is_pending =
+            context.is_some_and(|context| context.tasks.with(|tasks| !tasks.is_empty()));
+        // The fallback is shown again when the resources reload.
+        if is_pending && ready.get_untracked() {
+            start_timer();
+        }
+        pending.set(is_pending);
+    });
+
+    // Runs within the `Suspense`, unlike the closures of its parent.
+    let probe = move || suspense.set(Owner::current());
+
+    view! {
+        <ShowTransition
+            when=ready
+            name=name
+            fallback_name=fallback_name.unwrap_or(name)
+            mode=mode
+            fallback=fallback
+        >
+            <div>
+                <Suspense fallback=probe>{children.into_inner()()}</Suspense>
+            </div>
+        </ShowTransition>
+    }
+}
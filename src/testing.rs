@@ -0,0 +1,748 @@
+//! Utilities for testing components that use transitions. Requires the
+//! `testing` feature.
+//!
+//! Transitions provided with a [`MockScheduler`] via context don't touch
+//! `requestAnimationFrame` or `setTimeout`, frames and time only move when
+//! the test advances them.
+//!
+//! ``` rust
+//! use leptos::{prelude::*, task::tick};
+//! use leptos_transition_group::{testing::*, CSSTransition};
+//! use std::time::Duration;
+//!
+//! async fn fade_in(parent: web_sys::HtmlElement) {
+//!     let scheduler = MockScheduler::new();
+//!     scheduler.set_transition_info(Some(Duration::from_millis(300)));
+//!     let show = RwSignal::new(false);
+//!     let _handle = mount_to(parent, {
+//!         let scheduler = scheduler.clone();
+//!         move || {
+//!             provide_context(scheduler);
+//!             view! {
+//!                 <CSSTransition show=show name="fade">
+//!                     <div id="target" />
+//!                 </CSSTransition>
+//!             }
+//!         }
+//!     });
+//!     tick().await;
+//!
+//!     let el = document().get_element_by_id("target").unwrap();
+//!     let recorder = ClassListRecorder::new(&el);
+//!     show.set(true);
+//!     tick().await;
+//!     scheduler.advance_frames(2);
+//!     fire_transition_end(&el);
+//!
+//!     assert_eq!(
+//!         recorder.take(),
+//!         vec![
+//!             ClassListChange::new(&["fade-enter-from", "fade-enter-active"], &[]),
+//!             ClassListChange::new(&[], &["fade-enter-from"]),
+//!             ClassListChange::new(&["fade-enter-to"], &[]),
+//!             ClassListChange::new(&[], &["fade-enter-active", "fade-enter-to"]),
+//!         ]
+//!     );
+//! }
+//! ```
+
+use crate::{
+    AnimationTypes, CSSTransitionInfo, TransitionBackend, TransitionContext, TransitionHook,
+    TransitionPhase, TransitionStage,
+};
+use send_wrapper::SendWrapper;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+use web_sys::{
+    js_sys::Array,
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    Element, Event, MutationObserver, MutationObserverInit, MutationRecord,
+};
+
+/// A manually driven replacement for `requestAnimationFrame` and
+/// `setTimeout`.
+///
+/// Transitions use it when it is provided via context in the owner that
+/// renders them.
+#[derive(Clone)]
+pub struct MockScheduler(SendWrapper<Rc<RefCell<MockSchedulerInner>>>);
+
+type Callback = Box<dyn FnOnce()>;
+
+#[derive(Default)]
+struct MockSchedulerInner {
+    now: Duration,
+    next_id: u64,
+    frames: Vec<(u64, Callback)>,
+    reads: Vec<(u64, Callback)>,
+    timeouts: Vec<(u64, Duration, Callback)>,
+    transition_info: Option<Option<CSSTransitionInfo>>,
+}
+
+impl Default for MockScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockScheduler {
+    pub fn new() -> Self {
+        Self(SendWrapper::new(Default::default()))
+    }
+
+    /// The time elapsed since the scheduler was created.
+    pub fn now(&self) -> Duration {
+        self.0.borrow().now
+    }
+
+    /// The number of callbacks waiting for the next frame.
+    pub fn pending_frames(&self) -> usize {
+        let inner = self.0.borrow();
+        inner.frames.len() + inner.reads.len()
+    }
+
+    /// The number of timeouts that have not fired yet.
+    pub fn pending_timeouts(&self) -> usize {
+        self.0.borrow().timeouts.len()
+    }
+
+    /// Makes the transitions scheduled by this scheduler last `duration`
+    /// once the `*-to` classes are applied, `None` for no transition,
+    /// instead of reading their duration from the computed style.
+    pub fn set_transition_info(&self, duration: Option<Duration>) {
+        self.0.borrow_mut().transition_info = Some(duration.map(transition_info));
+    }
+
+    /// The transition set by [`Self::set_transition_info`], if any.
+    pub(crate) fn transition_info(&self) -> Option<Option<CSSTransitionInfo>> {
+        self.0.borrow().transition_info
+    }
+
+    /// Runs the frame callbacks requested before this frame, then the read
+    /// callbacks. Frame callbacks requested while it runs wait for the next
+    /// one, read callbacks run in this one.
+    pub fn advance_frame(&self) {
+        let ids = {
+            let inner = self.0.borrow();
+            inner.frames.iter().map(|(id, _)| *id).collect::<Vec<_>>()
+        };
+        for id in ids {
+            let cb = {
+                let mut inner = self.0.borrow_mut();
+                inner
+                    .frames
+                    .iter()
+                    .position(|(frame_id, _)| *frame_id == id)
+                    .map(|index| inner.frames.remove(index).1)
+            };
+            if let Some(cb) = cb {
+                cb();
+            }
+        }
+
+        loop {
+            let cb = {
+                let mut inner = self.0.borrow_mut();
+                (!inner.reads.is_empty()).then(|| inner.reads.remove(0).1)
+            };
+            let Some(cb) = cb else {
+                break;
+            };
+            cb();
+        }
+    }
+
+    pub fn advance_frames(&self, count: usize) {
+        for _ in 0..count {
+            self.advance_frame();
+        }
+    }
+
+    /// Moves the clock forward, firing the timeouts that become due in
+    /// order, including those set by the fired ones.
+    pub fn advance_by(&self, duration: Duration) {
+        let target = self.now() + duration;
+        loop {
+            let cb = {
+                let mut inner = self.0.borrow_mut();
+                let next = inner
+                    .timeouts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, deadline, _))| *deadline <= target)
+                    .min_by_key(|(_, (id, deadline, _))| (*deadline, *id))
+                    .map(|(index, _)| index);
+                next.map(|index| {
+                    let (_, deadline, cb) = inner.timeouts.remove(index);
+                    inner.now = deadline;
+                    cb
+                })
+            };
+            let Some(cb) = cb else {
+                break;
+            };
+            cb();
+        }
+        self.0.borrow_mut().now = target;
+    }
+
+    pub(crate) fn request_frame(&self, cb: impl FnOnce() + 'static) -> u64 {
+        let mut inner = self.0.borrow_mut();
+        let id = inner.next_id();
+        inner.frames.push((id, Box::new(cb)));
+        id
+    }
+
+    pub(crate) fn request_read(&self, cb: impl FnOnce() + 'static) -> u64 {
+        let mut inner = self.0.borrow_mut();
+        let id = inner.next_id();
+        inner.reads.push((id, Box::new(cb)));
+        id
+    }
+
+    pub(crate) fn set_timeout(&self, cb: impl FnOnce() + 'static, delay: Duration) -> u64 {
+        let mut inner = self.0.borrow_mut();
+        let id = inner.next_id();
+        let deadline = inner.now + delay;
+        inner.timeouts.push((id, deadline, Box::new(cb)));
+        id
+    }
+
+    pub(crate) fn cancel(&self, id: u64) {
+        let mut inner = self.0.borrow_mut();
+        inner.frames.retain(|(frame_id, _)| *frame_id != id);
+        inner.reads.retain(|(read_id, _)| *read_id != id);
+        inner
+            .timeouts
+            .retain(|(timeout_id, _, _)| *timeout_id != id);
+    }
+}
+
+impl MockSchedulerInner {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+// A single transitioned property lasting `duration`.
+fn transition_info(duration: Duration) -> CSSTransitionInfo {
+    CSSTransitionInfo {
+        types: AnimationTypes::Transition,
+        prop_count: 1,
+        timeout: duration.as_millis() as u64,
+    }
+}
+
+/// Dispatches a synthetic `transitionend` event on the element.
+pub fn fire_transition_end(el: &Element) {
+    fire(el, "transitionend");
+}
+
+/// Dispatches a synthetic `animationend` event on the element.
+pub fn fire_animation_end(el: &Element) {
+    fire(el, "animationend");
+}
+
+fn fire(el: &Element, event_name: &str) {
+    if let Ok(event) = Event::new(event_name) {
+        let _ = el.dispatch_event(&event);
+    }
+}
+
+/// The classes added and removed by a single change of the `class`
+/// attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassListChange {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// The stages whose classes were removed and added by a single change, see
+/// [`TransitionBackend::change_stages`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageChange {
+    pub phase: TransitionPhase,
+    pub removed: Vec<TransitionStage>,
+    pub added: Vec<TransitionStage>,
+}
+
+impl StageChange {
+    pub fn new(
+        phase: TransitionPhase,
+        removed: &[TransitionStage],
+        added: &[TransitionStage],
+    ) -> Self {
+        Self {
+            phase,
+            removed: removed.to_vec(),
+            added: added.to_vec(),
+        }
+    }
+}
+
+impl ClassListChange {
+    pub fn new(added: &[&str], removed: &[&str]) -> Self {
+        Self {
+            added: added.iter().map(|class| class.to_string()).collect(),
+            removed: removed.iter().map(|class| class.to_string()).collect(),
+        }
+    }
+
+    fn diff(old: &str, new: &str) -> Self {
+        let old = old.split_whitespace().collect::<Vec<_>>();
+        let new = new.split_whitespace().collect::<Vec<_>>();
+        Self {
+            added: new
+                .iter()
+                .filter(|class| !old.contains(class))
+                .map(|class| class.to_string())
+                .collect(),
+            removed: old
+                .iter()
+                .filter(|class| !new.contains(class))
+                .map(|class| class.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Records every change of an element's class list, in order.
+pub struct ClassListRecorder {
+    el: Element,
+    observer: MutationObserver,
+    // Old values delivered to the observer callback before `take`.
+    old_values: Rc<RefCell<Vec<String>>>,
+    _callback: Closure<dyn FnMut(Array, MutationObserver)>,
+}
+
+impl ClassListRecorder {
+    pub fn new(el: &Element) -> Self {
+        let old_values = Rc::new(RefCell::new(vec![]));
+        let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new({
+            let old_values = old_values.clone();
+            move |records: Array, _| {
+                old_values.borrow_mut().extend(Self::old_values(records));
+            }
+        });
+        let observer = MutationObserver::new(callback.as_ref().unchecked_ref())
+            .expect("MutationObserver::new");
+        let options = MutationObserverInit::new();
+        options.set_attributes(true);
+        options.set_attribute_old_value(true);
+        options.set_attribute_filter(&Array::of1(&JsValue::from_str("class")));
+        let _ = observer.observe_with_options(el, &options);
+
+        Self {
+            el: el.clone(),
+            observer,
+            old_values,
+            _callback: callback,
+        }
+    }
+
+    /// Returns the changes recorded since the last call.
+    pub fn take(&self) -> Vec<ClassListChange> {
+        let mut values = self.old_values.borrow_mut().drain(..).collect::<Vec<_>>();
+        values.extend(Self::old_values(self.observer.take_records()));
+        if values.is_empty() {
+            return vec![];
+        }
+        values.push(self.el.get_attribute("class").unwrap_or_default());
+        Self::changes(&values)
+    }
+
+    // The changes between successive values of the `class` attribute.
+    fn changes(values: &[String]) -> Vec<ClassListChange> {
+        values
+            .windows(2)
+            .map(|pair| ClassListChange::diff(&pair[0], &pair[1]))
+            .filter(|change| !change.added.is_empty() || !change.removed.is_empty())
+            .collect()
+    }
+
+    fn old_values(records: Array) -> Vec<String> {
+        records
+            .iter()
+            .map(|record| {
+                record
+                    .unchecked_into::<MutationRecord>()
+                    .old_value()
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+impl Drop for ClassListRecorder {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// An in-memory [`TransitionBackend`], so a
+/// [`TransitionMachine`](crate::TransitionMachine) can be driven under
+/// `cargo test` on the host.
+///
+/// ``` rust
+/// use leptos_transition_group::{testing::*, TransitionHook, TransitionMachine};
+/// use std::time::Duration;
+///
+/// let scheduler = MockScheduler::new();
+/// let machine = TransitionMachine::new(MemoryBackend::new(scheduler.clone()));
+/// machine.backend().set_transition_info(Some(Duration::from_millis(300)));
+///
+/// machine.enter("fade", Duration::ZERO, false);
+/// scheduler.advance_frames(2);
+/// assert_eq!(machine.backend().classes(), ["fade-enter-active", "fade-enter-to"]);
+///
+/// machine.backend().fire_end();
+/// assert!(machine.backend().classes().is_empty());
+/// assert_eq!(
+///     machine.backend().take_hooks(),
+///     [
+///         TransitionHook::BeforeEnter,
+///         TransitionHook::Enter,
+///         TransitionHook::AfterEnter
+///     ]
+/// );
+/// ```
+pub struct MemoryBackend {
+    scheduler: MockScheduler,
+    classes: RefCell<Vec<String>>,
+    changes: RefCell<Vec<ClassListChange>>,
+    stage_changes: RefCell<Vec<StageChange>>,
+    visible: Cell<bool>,
+    info: Cell<Option<CSSTransitionInfo>>,
+    next_listener_id: Cell<u64>,
+    end_listeners: RefCell<Vec<EndListener>>,
+    hooks: RefCell<Vec<(TransitionHook, TransitionContext)>>,
+}
+
+type EndListener = (u64, Rc<dyn Fn()>);
+
+pub enum MemoryHandle {
+    Schedule(u64),
+    Listener(u64),
+}
+
+impl MemoryBackend {
+    pub fn new(scheduler: MockScheduler) -> Self {
+        Self {
+            scheduler,
+            classes: Default::default(),
+            changes: Default::default(),
+            stage_changes: Default::default(),
+            visible: Cell::new(true),
+            info: Cell::new(None),
+            next_listener_id: Cell::new(0),
+            end_listeners: Default::default(),
+            hooks: Default::default(),
+        }
+    }
+
+    /// Sets the duration of the transition the element has once the `*-to`
+    /// classes are applied, `None` for no transition.
+    pub fn set_transition_info(&self, duration: Option<Duration>) {
+        self.info.set(duration.map(transition_info));
+    }
+
+    /// The current classes, in insertion order.
+    pub fn classes(&self) -> Vec<String> {
+        self.classes.borrow().clone()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    /// Returns the class list changes made since the last call.
+    pub fn take_changes(&self) -> Vec<ClassListChange> {
+        self.changes.take()
+    }
+
+    /// Returns the stage changes made since the last call.
+    pub fn take_stage_changes(&self) -> Vec<StageChange> {
+        self.stage_changes.take()
+    }
+
+    /// Returns the hooks reported since the last call.
+    pub fn take_hooks(&self) -> Vec<TransitionHook> {
+        self.take_hooks_with_context()
+            .into_iter()
+            .map(|(hook, _)| hook)
+            .collect()
+    }
+
+    /// Returns the hooks reported since the last call, with the state of
+    /// the transition.
+    pub fn take_hooks_with_context(&self) -> Vec<(TransitionHook, TransitionContext)> {
+        self.hooks.take()
+    }
+
+    /// Dispatches a synthetic end event to the end listeners.
+    pub fn fire_end(&self) {
+        let listeners = self
+            .end_listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect::<Vec<_>>();
+        for listener in listeners {
+            listener();
+        }
+    }
+
+    fn change_classes(&self, f: impl FnOnce(&mut Vec<String>)) {
+        let mut classes = self.classes.borrow_mut();
+        let old = classes.join(" ");
+        f(&mut classes);
+        let change = ClassListChange::diff(&old, &classes.join(" "));
+        if !change.added.is_empty() || !change.removed.is_empty() {
+            self.changes.borrow_mut().push(change);
+        }
+    }
+}
+
+impl TransitionBackend for MemoryBackend {
+    type Handle = MemoryHandle;
+
+    fn add_classes(&self, added: &[&str]) {
+        self.change_classes(|classes| {
+            for class in added {
+                if !classes.iter().any(|c| c == class) {
+                    classes.push(class.to_string());
+                }
+            }
+        });
+    }
+
+    fn remove_classes(&self, removed: &[&str]) {
+        self.change_classes(|classes| classes.retain(|class| !removed.contains(&class.as_str())));
+    }
+
+    fn change_stages(
+        &self,
+        phase: TransitionPhase,
+        removed: &[TransitionStage],
+        added: &[TransitionStage],
+    ) {
+        self.stage_changes
+            .borrow_mut()
+            .push(StageChange::new(phase, removed, added));
+    }
+
+    fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+    }
+
+    fn transition_info(&self) -> Option<CSSTransitionInfo> {
+        self.info.get()
+    }
+
+    fn request_frame(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        MemoryHandle::Schedule(self.scheduler.request_frame(cb))
+    }
+
+    fn request_read(&self, cb: Box<dyn FnOnce()>) -> Self::Handle {
+        MemoryHandle::Schedule(self.scheduler.request_read(cb))
+    }
+
+    fn set_timeout(&self, cb: Box<dyn FnOnce()>, delay: Duration) -> Self::Handle {
+        MemoryHandle::Schedule(self.scheduler.set_timeout(cb, delay))
+    }
+
+    fn listen_end(&self, _types: AnimationTypes, cb: Box<dyn Fn()>) -> Self::Handle {
+        let id = self.next_listener_id.get() + 1;
+        self.next_listener_id.set(id);
+        self.end_listeners.borrow_mut().push((id, Rc::from(cb)));
+        MemoryHandle::Listener(id)
+    }
+
+    fn cancel(&self, handle: Self::Handle) {
+        match handle {
+            MemoryHandle::Schedule(id) => self.scheduler.cancel(id),
+            MemoryHandle::Listener(id) => self
+                .end_listeners
+                .borrow_mut()
+                .retain(|(listener_id, _)| *listener_id != id),
+        }
+    }
+
+    fn notify(&self, hook: TransitionHook, context: &TransitionContext) {
+        self.hooks.borrow_mut().push((hook, *context));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> (
+        Rc<RefCell<Vec<&'static str>>>,
+        impl Fn(&'static str) -> Callback,
+    ) {
+        let log = Rc::new(RefCell::new(vec![]));
+        let push = {
+            let log = log.clone();
+            move |name: &'static str| -> Callback {
+                let log = log.clone();
+                Box::new(move || log.borrow_mut().push(name))
+            }
+        };
+        (log, push)
+    }
+
+    #[test]
+    fn advance_frame_runs_writes_before_reads() {
+        let scheduler = MockScheduler::new();
+        let (log, push) = log();
+
+        scheduler.request_read(push("read"));
+        scheduler.request_frame(push("write 1"));
+        scheduler.request_frame({
+            let scheduler = scheduler.clone();
+            let write = push("next write");
+            let read = push("nested read");
+            move || {
+                scheduler.request_frame(write);
+                scheduler.request_read(read);
+            }
+        });
+        assert_eq!(scheduler.pending_frames(), 3);
+
+        scheduler.advance_frame();
+        assert_eq!(*log.borrow(), ["write 1", "read", "nested read"]);
+        assert_eq!(scheduler.pending_frames(), 1);
+
+        scheduler.advance_frame();
+        assert_eq!(log.borrow().last(), Some(&"next write"));
+        assert_eq!(scheduler.pending_frames(), 0);
+    }
+
+    #[test]
+    fn advance_by_fires_due_timeouts_in_order() {
+        let scheduler = MockScheduler::new();
+        let (log, push) = log();
+
+        scheduler.set_timeout(push("30"), Duration::from_millis(30));
+        scheduler.set_timeout(push("10 a"), Duration::from_millis(10));
+        scheduler.set_timeout(push("10 b"), Duration::from_millis(10));
+        scheduler.set_timeout(
+            {
+                let scheduler = scheduler.clone();
+                let nested = push("20 + 5");
+                move || {
+                    assert_eq!(scheduler.now(), Duration::from_millis(20));
+                    scheduler.set_timeout(nested, Duration::from_millis(5));
+                }
+            },
+            Duration::from_millis(20),
+        );
+
+        scheduler.advance_by(Duration::from_millis(9));
+        assert!(log.borrow().is_empty());
+        scheduler.advance_by(Duration::from_millis(16));
+        assert_eq!(*log.borrow(), ["10 a", "10 b", "20 + 5"]);
+        assert_eq!(scheduler.now(), Duration::from_millis(25));
+        assert_eq!(scheduler.pending_timeouts(), 1);
+
+        scheduler.advance_by(Duration::from_millis(5));
+        assert_eq!(log.borrow().last(), Some(&"30"));
+        assert_eq!(scheduler.pending_timeouts(), 0);
+    }
+
+    #[test]
+    fn cancelled_callbacks_dont_run() {
+        let scheduler = MockScheduler::new();
+        let (log, push) = log();
+
+        let frame = scheduler.request_frame(push("frame"));
+        let read = scheduler.request_read(push("read"));
+        let timeout = scheduler.set_timeout(push("timeout"), Duration::from_millis(10));
+        scheduler.request_frame(push("kept"));
+        for id in [frame, read, timeout] {
+            scheduler.cancel(id);
+        }
+        assert_eq!(scheduler.pending_frames(), 1);
+        assert_eq!(scheduler.pending_timeouts(), 0);
+
+        scheduler.advance_frame();
+        scheduler.advance_by(Duration::from_millis(10));
+        assert_eq!(*log.borrow(), ["kept"]);
+    }
+
+    #[test]
+    fn frame_cancelled_by_an_earlier_one_doesnt_run() {
+        let scheduler = MockScheduler::new();
+        let (log, push) = log();
+
+        let cancelled = Rc::new(Cell::new(None));
+        scheduler.request_frame({
+            let scheduler = scheduler.clone();
+            let cancelled = cancelled.clone();
+            move || scheduler.cancel(cancelled.get().unwrap())
+        });
+        cancelled.set(Some(scheduler.request_frame(push("cancelled"))));
+
+        scheduler.advance_frame();
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn transition_info() {
+        let scheduler = MockScheduler::new();
+        assert_eq!(scheduler.transition_info(), None);
+        scheduler.set_transition_info(None);
+        assert_eq!(scheduler.transition_info(), Some(None));
+        scheduler.set_transition_info(Some(Duration::from_millis(300)));
+        assert_eq!(
+            scheduler
+                .transition_info()
+                .flatten()
+                .map(|info| info.timeout),
+            Some(300)
+        );
+    }
+
+    #[test]
+    fn recorder_changes() {
+        let values = [
+            "a",
+            "a b-from b-active",
+            "a b-active  b-from",
+            "a b-active b-to",
+            "a",
+        ]
+        .map(String::from);
+        assert_eq!(
+            ClassListRecorder::changes(&values),
+            [
+                ClassListChange::new(&["b-from", "b-active"], &[]),
+                ClassListChange::new(&["b-to"], &["b-from"]),
+                ClassListChange::new(&[], &["b-active", "b-to"]),
+            ]
+        );
+        assert!(ClassListRecorder::changes(&["a".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn memory_backend_records_class_changes() {
+        let backend = MemoryBackend::new(MockScheduler::new());
+        backend.add_classes(&["a", "b"]);
+        backend.add_classes(&["a"]);
+        backend.remove_classes(&["a", "c"]);
+        assert_eq!(backend.classes(), ["b"]);
+        assert_eq!(
+            backend.take_changes(),
+            [
+                ClassListChange::new(&["a", "b"], &[]),
+                ClassListChange::new(&[], &["a"]),
+            ]
+        );
+        assert!(backend.take_changes().is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::CSSTransitionInfo;
+use leptos::prelude::document;
+use std::sync::atomic::{AtomicU64, Ordering};
+use web_sys::{
+    js_sys::{Array, Function, Reflect},
+    wasm_bindgen::{JsCast, JsValue},
+    Element, HtmlElement,
+};
+
+static TIME_SCALE: AtomicU64 = AtomicU64::new(f64::to_bits(1.0));
+
+/// Multiplies the duration and delay of every transition, e.g. `10.0` plays
+/// them 10x slower. Meant for debugging, a large value practically pauses
+/// transitions for inspection. `1.0` restores the CSS durations.
+///
+/// The scale is also exposed as the `--transition-time-scale` CSS variable
+/// on the root element, for durations that CSS computes itself.
+///
+/// ``` rust,no_run
+/// #[cfg(debug_assertions)]
+/// leptos_transition_group::set_time_scale(10.0);
+/// ```
+pub fn set_time_scale(scale: f64) {
+    let scale = if scale.is_finite() && scale > 0.0 {
+        scale
+    } else {
+        1.0
+    };
+    TIME_SCALE.store(scale.to_bits(), Ordering::Relaxed);
+
+    if let Some(root) = document().document_element() {
+        if let Ok(root) = root.dyn_into::<HtmlElement>() {
+            let _ = root
+                .style()
+                .set_property("--transition-time-scale", &scale.to_string());
+        }
+    }
+}
+
+/// The scale set by [`set_time_scale`].
+pub fn time_scale() -> f64 {
+    f64::from_bits(TIME_SCALE.load(Ordering::Relaxed))
+}
+
+/// Plays the transitions and animations that the element is running at
+/// the time scale, and scales `info` accordingly.
+///
+/// Called in the read phase once the `*-to` classes are applied, so the
+/// durations set by them are scaled too, without touching the inline style.
+pub(crate) fn scale_running(
+    el: &Element,
+    info: Option<CSSTransitionInfo>,
+) -> Option<CSSTransitionInfo> {
+    let scale = time_scale();
+    if scale == 1.0 {
+        return info;
+    }
+    let animations = Reflect::get(el, &JsValue::from_str("getAnimations"))
+        .ok()
+        .and_then(|get_animations| get_animations.dyn_into::<Function>().ok())
+        .and_then(|get_animations| get_animations.call0(el).ok())
+        .and_then(|animations| animations.dyn_into::<Array>().ok());
+    for animation in animations.iter().flat_map(Array::iter) {
+        let _ = Reflect::set(
+            &animation,
+            &JsValue::from_str("playbackRate"),
+            &JsValue::from_f64(1.0 / scale),
+        );
+    }
+
+    info.map(|info| CSSTransitionInfo {
+        timeout: (info.timeout as f64 * scale) as u64,
+        ..info
+    })
+}
@@ -0,0 +1,8 @@
+/// Emits a `tracing` debug event when the `tracing` feature is enabled,
+/// otherwise expands to nothing.
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    }};
+}
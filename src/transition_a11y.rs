@@ -0,0 +1,95 @@
+use leptos::prelude::document;
+use web_sys::{wasm_bindgen::JsCast, Element, HtmlElement};
+
+/// Accessibility options of `CSSTransition`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TransitionA11y {
+    /// Sets `inert` and `aria-hidden="true"` while the element is leaving
+    /// or hidden, so that it can't be focused or read by screen readers.
+    pub inert: bool,
+    /// Focuses the element matching the selector after the enter
+    /// transition, or the element itself if it matches.
+    pub autofocus: Option<String>,
+    /// Moves the focus back to the element focused before the enter
+    /// transition after the leave transition, if the focus was inside
+    /// the leaving element.
+    pub restore_focus: bool,
+}
+
+impl TransitionA11y {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn inert(mut self) -> Self {
+        self.inert = true;
+        self
+    }
+
+    pub fn autofocus(mut self, selector: impl Into<String>) -> Self {
+        self.autofocus = Some(selector.into());
+        self
+    }
+
+    pub fn restore_focus(mut self) -> Self {
+        self.restore_focus = true;
+        self
+    }
+
+    pub(crate) fn set_hidden(&self, el: &Element, hidden: bool) {
+        if !self.inert {
+            return;
+        }
+        if hidden {
+            let _ = el.set_attribute("inert", "");
+            let _ = el.set_attribute("aria-hidden", "true");
+        } else {
+            let _ = el.remove_attribute("inert");
+            let _ = el.remove_attribute("aria-hidden");
+        }
+    }
+
+    /// The focused element to restore after the leave transition.
+    pub(crate) fn focus_origin(&self, el: &Element) -> Option<Element> {
+        if !self.restore_focus {
+            return None;
+        }
+        document()
+            .active_element()
+            .filter(|active| !el.contains(Some(active)))
+    }
+
+    pub(crate) fn autofocus_in(&self, el: &Element) {
+        let Some(selector) = &self.autofocus else {
+            return;
+        };
+        let target = if el.matches(selector).unwrap_or_default() {
+            Some(el.clone())
+        } else {
+            el.query_selector(selector).ok().flatten()
+        };
+        focus(target);
+    }
+
+    pub(crate) fn restore_focus_from(&self, el: &Element, origin: Option<Element>) {
+        let Some(origin) = origin else {
+            return;
+        };
+        // Unless the focus has been moved elsewhere meanwhile.
+        let focus_lost = document().active_element().is_none_or(|active| {
+            el.contains(Some(&active))
+                || document()
+                    .body()
+                    .is_some_and(|body| body.is_same_node(Some(&active)))
+        });
+        if focus_lost && origin.is_connected() {
+            focus(Some(origin));
+        }
+    }
+}
+
+fn focus(el: Option<Element>) {
+    if let Some(el) = el.as_ref().and_then(|el| el.dyn_ref::<HtmlElement>()) {
+        let _ = el.focus();
+    }
+}
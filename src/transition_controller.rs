@@ -0,0 +1,289 @@
+use futures::channel::oneshot;
+use leptos::prelude::*;
+use std::future::Future;
+
+/// How a transition requested through [`TransitionController`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionOutcome {
+    /// The transition ran until its end.
+    Finished,
+    /// The transition was interrupted by an opposite transition, or the
+    /// component was unmounted before it ended.
+    Cancelled,
+}
+
+/// Where a `CSSTransition` driven by a [`TransitionController`] is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransitionStatus {
+    Entering,
+    /// Shown, including after a cancelled transition.
+    Entered,
+    Leaving,
+    /// Hidden.
+    #[default]
+    Left,
+}
+
+/// An imperative handle to a `CSSTransition`.
+///
+/// ``` rust
+/// use leptos::{prelude::*, task::spawn_local};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let controller = TransitionController::new();
+/// let show = RwSignal::new(false);
+/// let input_ref = NodeRef::<leptos::html::Input>::new();
+///
+/// let open = move |_| {
+///     spawn_local(async move {
+///         if controller.enter().await == TransitionOutcome::Finished {
+///             let _ = input_ref.get_untracked().unwrap().focus();
+///         }
+///     })
+/// };
+///
+/// view! {
+///     <button on:click=open>"Open"</button>
+///     <CSSTransition show=show name="fade" controller=controller>
+///         <div><input node_ref=input_ref /></div>
+///     </CSSTransition>
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct TransitionController {
+    show: RwSignal<bool>,
+    status: RwSignal<TransitionStatus>,
+    waiters: StoredValue<Vec<(bool, oneshot::Sender<TransitionOutcome>)>>,
+    /// Ends the running transition of the attached component, `true` to
+    /// cancel it.
+    finish: StoredValue<Option<FinishFn>>,
+}
+
+type FinishFn = Box<dyn Fn(bool) + Send + Sync>;
+
+impl Default for TransitionController {
+    fn default() -> Self {
+        Self {
+            show: RwSignal::new(false),
+            status: RwSignal::new(TransitionStatus::default()),
+            waiters: StoredValue::new(vec![]),
+            finish: StoredValue::new(None),
+        }
+    }
+}
+
+impl TransitionController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the content. The returned future resolves when the enter
+    /// transition ends or is cancelled.
+    pub fn enter(&self) -> impl Future<Output = TransitionOutcome> + Send + 'static {
+        self.request(true)
+    }
+
+    /// Hides the content. The returned future resolves when the leave
+    /// transition ends or is cancelled.
+    pub fn leave(&self) -> impl Future<Output = TransitionOutcome> + Send + 'static {
+        self.request(false)
+    }
+
+    /// Enters if the content is hidden, leaves otherwise.
+    pub fn toggle(&self) -> impl Future<Output = TransitionOutcome> + Send + 'static {
+        self.request(!self.show.get_untracked())
+    }
+
+    /// Jumps to the end of the running transition, as if it had ended
+    /// naturally.
+    pub fn finish(&self) {
+        self.finish.with_value(|finish| {
+            if let Some(finish) = finish {
+                finish(false);
+            }
+        });
+    }
+
+    /// Cancels the running transition, calling the `*_cancelled` hooks.
+    /// Its classes are removed, so the content snaps to its shown state
+    /// instead of stopping where it is. Pending futures resolve with
+    /// [`TransitionOutcome::Cancelled`].
+    pub fn cancel(&self) {
+        self.finish.with_value(|finish| {
+            if let Some(finish) = finish {
+                finish(true);
+            }
+        });
+        self.cancel_all();
+        // A cancelled leave leaves the content shown, the component doesn't
+        // transition when `show` follows.
+        if self.status.get_untracked() == TransitionStatus::Entered && !self.show.get_untracked() {
+            self.show.set(true);
+        }
+    }
+
+    /// The current status, tracked.
+    pub fn status(&self) -> TransitionStatus {
+        self.status.get()
+    }
+
+    fn request(&self, show: bool) -> impl Future<Output = TransitionOutcome> + Send + 'static {
+        let (tx, rx) = oneshot::channel();
+        self.waiters
+            .update_value(|waiters| waiters.push((show, tx)));
+        self.show.set(show);
+
+        async move { rx.await.unwrap_or(TransitionOutcome::Cancelled) }
+    }
+
+    /// Merges the `show` prop of the component into the controller state,
+    /// which then drives the component.
+    pub(crate) fn bind(&self, show: Signal<bool>) -> Signal<bool> {
+        let state = self.show;
+        state.set(show.get_untracked());
+        Effect::watch(
+            move || show.get(),
+            move |show, _, _| state.set(*show),
+            false,
+        );
+
+        state.into()
+    }
+
+    /// Attaches the component that is driven by this controller.
+    pub(crate) fn attach(&self, finish: impl Fn(bool) + Send + Sync + 'static) {
+        self.finish.set_value(Some(Box::new(finish)));
+    }
+
+    pub(crate) fn detach(&self) {
+        self.finish.set_value(None);
+    }
+
+    /// Changes the `show` state without waiting for the transition.
+    pub(crate) fn set_show(&self, show: bool) {
+        self.show.set(show);
+    }
+
+    pub(crate) fn status_untracked(&self) -> TransitionStatus {
+        self.status.get_untracked()
+    }
+
+    pub(crate) fn set_status(&self, status: TransitionStatus) {
+        self.status.set(status);
+    }
+
+    /// Resolves every pending future waiting for the `show` state.
+    pub(crate) fn settle(&self, show: bool, outcome: TransitionOutcome) {
+        self.waiters.update_value(|waiters| {
+            let (settled, pending): (Vec<_>, Vec<_>) =
+                waiters.drain(..).partition(|(target, _)| *target == show);
+            *waiters = pending;
+            for (_, tx) in settled {
+                let _ = tx.send(outcome);
+            }
+        });
+    }
+
+    /// Cancels every pending future.
+    pub(crate) fn cancel_all(&self) {
+        self.waiters.update_value(|waiters| {
+            for (_, tx) in waiters.drain(..) {
+                let _ = tx.send(TransitionOutcome::Cancelled);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    /// Attaches a component whose cancelled transitions leave the content
+    /// shown, as `CSSTransition` does.
+    fn attach(controller: TransitionController) {
+        controller.attach(move |cancelled| {
+            controller.set_status(if cancelled || controller.show.get_untracked() {
+                TransitionStatus::Entered
+            } else {
+                TransitionStatus::Left
+            });
+        });
+    }
+
+    #[test]
+    fn settle() {
+        let controller = TransitionController::new();
+        let mut enter = Box::pin(controller.enter());
+        let leave = controller.leave();
+        assert!(!controller.show.get_untracked());
+
+        controller.settle(false, TransitionOutcome::Finished);
+        assert_eq!(enter.as_mut().now_or_never(), None);
+        assert_eq!(leave.now_or_never(), Some(TransitionOutcome::Finished));
+
+        controller.settle(true, TransitionOutcome::Cancelled);
+        assert_eq!(enter.now_or_never(), Some(TransitionOutcome::Cancelled));
+    }
+
+    #[test]
+    fn cancel_all() {
+        let controller = TransitionController::new();
+        let enter = controller.enter();
+        let leave = controller.leave();
+
+        controller.cancel_all();
+        assert_eq!(enter.now_or_never(), Some(TransitionOutcome::Cancelled));
+        assert_eq!(leave.now_or_never(), Some(TransitionOutcome::Cancelled));
+    }
+
+    #[test]
+    fn toggle() {
+        let controller = TransitionController::new();
+        let enter = controller.toggle();
+        assert!(controller.show.get_untracked());
+        let leave = controller.toggle();
+        assert!(!controller.show.get_untracked());
+
+        controller.settle(false, TransitionOutcome::Finished);
+        assert_eq!(leave.now_or_never(), Some(TransitionOutcome::Finished));
+        controller.settle(true, TransitionOutcome::Finished);
+        assert_eq!(enter.now_or_never(), Some(TransitionOutcome::Finished));
+    }
+
+    #[test]
+    fn cancel_leave_resyncs_show() {
+        let controller = TransitionController::new();
+        attach(controller);
+        controller.set_show(true);
+        controller.set_status(TransitionStatus::Leaving);
+        let leave = controller.leave();
+
+        controller.cancel();
+        assert_eq!(leave.now_or_never(), Some(TransitionOutcome::Cancelled));
+        assert_eq!(controller.status_untracked(), TransitionStatus::Entered);
+        assert!(controller.show.get_untracked());
+    }
+
+    #[test]
+    fn cancel_enter_keeps_show() {
+        let controller = TransitionController::new();
+        attach(controller);
+        let enter = controller.enter();
+        controller.set_status(TransitionStatus::Entering);
+
+        controller.cancel();
+        assert_eq!(enter.now_or_never(), Some(TransitionOutcome::Cancelled));
+        assert_eq!(controller.status_untracked(), TransitionStatus::Entered);
+        assert!(controller.show.get_untracked());
+    }
+
+    #[test]
+    fn cancel_without_transition() {
+        let controller = TransitionController::new();
+        controller.cancel();
+        assert_eq!(controller.status_untracked(), TransitionStatus::Left);
+        assert!(!controller.show.get_untracked());
+    }
+}
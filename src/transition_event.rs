@@ -0,0 +1,43 @@
+use crate::get_transition_info::CSSTransitionInfo;
+use std::{fmt, time::Duration};
+use web_sys::Element;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    Enter,
+    Leave,
+}
+
+impl fmt::Display for TransitionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enter => f.write_str("enter"),
+            Self::Leave => f.write_str("leave"),
+        }
+    }
+}
+
+/// The context passed to [`TransitionHooks`](crate::TransitionHooks).
+#[derive(Debug, Clone)]
+pub struct TransitionEvent {
+    /// The transitioned element, an HTML, SVG or MathML element.
+    pub el: Element,
+    pub phase: TransitionPhase,
+    /// Whether this is the enter transition of the initial render.
+    pub appear: bool,
+    /// Whether this transition interrupted the previous one.
+    pub interrupted: bool,
+    /// The computed styles of the transition. `None` before the `*-to`
+    /// classes are applied, or if the element has no transition or
+    /// animation.
+    pub info: Option<CSSTransitionInfo>,
+}
+
+impl TransitionEvent {
+    /// The time the transition takes, including delays.
+    pub fn duration(&self) -> Option<Duration> {
+        self.info
+            .as_ref()
+            .map(|info| Duration::from_millis(info.timeout))
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{CSSTransition, StartStrategy};
+use leptos::{prelude::*, tachys::html::style::style};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The order in which staggered items start their transitions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StaggerOrder {
+    /// The first item in the list starts first.
+    #[default]
+    Forward,
+    /// The last item in the list starts first.
+    Reverse,
+}
+
+/// Delays the transitions of items that enter or leave together, so that
+/// they animate one after another.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stagger {
+    /// The delay added for each index.
+    pub delay: Duration,
+    /// The upper bound of the delay of a single item.
+    pub max: Option<Duration>,
+    pub order: StaggerOrder,
+}
+
+impl Stagger {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Default::default()
+        }
+    }
+
+    fn delay(&self, index: usize) -> Duration {
+        let delay = self.delay.saturating_mul(index as u32);
+        match self.max {
+            Some(max) => delay.min(max),
+            None => delay,
+        }
+    }
+}
+
+impl From<Duration> for Stagger {
+    fn from(delay: Duration) -> Self {
+        Self::new(delay)
+    }
+}
+
+/// # TransitionGroup
+///
+/// Applies enter and leave animations on the items of a keyed list.
+/// Items removed from `each` are kept in place until their leave
+/// transition ends.
+///
+/// Each item is rendered in a `CSSTransition`, so the view returned by
+/// `children` must have a single root element.
+///
+/// Items entering or leaving together are numbered in stagger order. The
+/// number is set as the `--transition-index` CSS custom property on each
+/// item, which can be used for CSS-only staggering:
+///
+/// ``` css
+/// .list-enter-active {
+///     transition: opacity 0.3s ease;
+///     transition-delay: calc(var(--transition-index) * 50ms);
+/// }
+/// ```
+///
+/// ## Examples
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use std::time::Duration;
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let items = RwSignal::new(vec![1, 2, 3]);
+/// view! {
+///     <TransitionGroup
+///         each=move || items.get()
+///         key=|item| *item
+///         name="list"
+///         stagger=Duration::from_millis(50)
+///         let:item
+///     >
+///         <li>{item}</li>
+///     </TransitionGroup>
+/// }
+/// # }
+/// ```
+#[component]
+pub fn TransitionGroup<IF, I, T, KF, K, CF, V>(
+    /// Items over which the component should iterate.
+    each: IF,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// Used to automatically generate transition CSS class names.
+    #[prop(into)]
+    name: Signal<String>,
+    /// Whether to apply transition to the items of the initial render.
+    #[prop(optional)]
+    appear: bool,
+    /// Delays items that enter or leave together.
+    #[prop(optional, into)]
+    stagger: Option<Stagger>,
+    /// Caches the computed durations of the items, see
+    /// `CSSTransition`'s `cache_info`.
+    #[prop(optional)]
+    cache_info: bool,
+    /// How the transitions of the items start, see `CSSTransition`'s
+    /// `start_strategy`.
+    #[prop(optional)]
+    start_strategy: StartStrategy,
+    /// CSS custom properties set on the items while they transition, see
+    /// `CSSTransition`'s `vars`.
+    #[prop(optional, into)]
+    vars: Option<Signal<Vec<(String, String)>>>,
+    children: CF,
+) -> impl IntoView
+where
+    IF: Fn() -> I + Send + Sync + 'static,
+    I: IntoIterator<Item = T>,
+    T: Clone + Send + Sync + 'static,
+    KF: Fn(&T) -> K + Send + Sync + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    CF: Fn(T) -> V + Send + Clone + 'static,
+    V: AddAnyAttr + IntoView + Send + 'static,
+{
+    let stagger = stagger.unwrap_or_default();
+    let vars = vars.unwrap_or_else(|| Signal::stored(vec![]));
+    let entries = RwSignal::new(Vec::<GroupEntry<K, T>>::new());
+
+    let update = move |items: Vec<T>, appear: bool| {
+        let prev = entries.get_untracked();
+        let next = diff(&prev, items, &key, &stagger, appear);
+        entries.set(next);
+    };
+    let each = move || each().into_iter().collect::<Vec<_>>();
+    update(each(), appear);
+    Effect::watch(each, move |items, _, _| update(items.clone(), true), false);
+
+    let children = move |entry: GroupEntry<K, T>| {
+        let GroupEntry {
+            id,
+            item,
+            show,
+            appear,
+            index,
+            delay,
+            ..
+        } = entry;
+        let on_after_leave = {
+            let show = show.clone();
+            move |_: web_sys::Element| {
+                if !show.get_untracked() {
+                    entries.update(|entries| entries.retain(|entry| entry.id != id));
+                }
+            }
+        };
+        let child = children(item).add_any_attr(style(("--transition-index", move || {
+            index.get().to_string()
+        })));
+
+        view! {
+            <CSSTransition
+                show=show
+                name=name
+                appear=appear
+                delay=delay
+                cache_info=cache_info
+                start_strategy=start_strategy
+                vars=vars
+                on_after_leave=on_after_leave
+            >
+                {child}
+            </CSSTransition>
+        }
+    };
+
+    view! { <For each=move || entries.get() key=|entry| entry.id children=children /> }
+}
+
+struct GroupEntry<K, T> {
+    id: u64,
+    key: K,
+    item: T,
+    show: ArcRwSignal<bool>,
+    appear: bool,
+    index: ArcRwSignal<usize>,
+    delay: ArcRwSignal<Duration>,
+}
+
+impl<K: Clone, T: Clone> Clone for GroupEntry<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            key: self.key.clone(),
+            item: self.item.clone(),
+            show: self.show.clone(),
+            appear: self.appear,
+            index: self.index.clone(),
+            delay: self.delay.clone(),
+        }
+    }
+}
+
+impl<K, T> GroupEntry<K, T> {
+    fn new(key: K, item: T, appear: bool) -> Self {
+        static ID: AtomicU64 = AtomicU64::new(0);
+
+        Self {
+            id: ID.fetch_add(1, Ordering::Relaxed),
+            key,
+            item,
+            show: ArcRwSignal::new(true),
+            appear,
+            index: ArcRwSignal::new(0),
+            delay: ArcRwSignal::new(Duration::ZERO),
+        }
+    }
+}
+
+/// Merges the new items into the current entries. Removed entries are kept
+/// after the entry that preceded them, and start leaving.
+fn diff<K, T, KF>(
+    prev: &[GroupEntry<K, T>],
+    items: Vec<T>,
+    key: &KF,
+    stagger: &Stagger,
+    appear: bool,
+) -> Vec<GroupEntry<K, T>>
+where
+    K: Eq + Hash + Clone,
+    T: Clone,
+    KF: Fn(&T) -> K,
+{
+    let mut by_key = HashMap::with_capacity(prev.len());
+    for entry in prev {
+        by_key.entry(&entry.key).or_insert(entry);
+    }
+
+    let mut kept = Vec::with_capacity(items.len());
+    let mut entering = HashSet::new();
+    for item in items {
+        let key = key(&item);
+        match by_key.get(&key) {
+            Some(entry) => {
+                if !entry.show.get_untracked() {
+                    entering.insert(entry.id);
+                }
+                kept.push(GroupEntry {
+                    item,
+                    ..(*entry).clone()
+                });
+            }
+            None => {
+                let entry = GroupEntry::new(key, item, appear);
+                entering.insert(entry.id);
+                kept.push(entry);
+            }
+        }
+    }
+
+    // The removed entries following each kept entry, or the start of the
+    // list, in their previous order.
+    let kept_ids = kept.iter().map(|entry| entry.id).collect::<HashSet<_>>();
+    let mut leading = vec![];
+    let mut following = HashMap::<u64, Vec<GroupEntry<K, T>>>::new();
+    let mut anchor = None;
+    let mut leaving = HashSet::new();
+    for entry in prev {
+        if kept_ids.contains(&entry.id) {
+            anchor = Some(entry.id);
+            continue;
+        }
+        if entry.show.get_untracked() {
+            leaving.insert(entry.id);
+        }
+        match anchor {
+            Some(anchor) => following.entry(anchor).or_default().push(entry.clone()),
+            None => leading.push(entry.clone()),
+        }
+    }
+
+    let mut next = leading;
+    next.reserve(kept.len() + following.values().map(Vec::len).sum::<usize>());
+    for entry in kept {
+        let removed = following.remove(&entry.id);
+        next.push(entry);
+        next.extend(removed.into_iter().flatten());
+    }
+
+    for (ids, show) in [(entering, true), (leaving, false)] {
+        let mut batch = next
+            .iter()
+            .filter(|entry| ids.contains(&entry.id))
+            .collect::<Vec<_>>();
+        if stagger.order == StaggerOrder::Reverse {
+            batch.reverse();
+        }
+        for (index, entry) in batch.into_iter().enumerate() {
+            entry.index.set(index);
+            entry.delay.set(stagger.delay(index));
+            entry.show.set(show);
+        }
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(entries: &[GroupEntry<u32, u32>]) -> Vec<(u32, bool)> {
+        entries
+            .iter()
+            .map(|entry| (entry.key, entry.show.get_untracked()))
+            .collect()
+    }
+
+    /// The index and delay in ms of the entries of `keys`, which only
+    /// matter for the entries that are entering or leaving.
+    fn staggered(entries: &[GroupEntry<u32, u32>], keys: &[u32]) -> Vec<(u32, usize, u64)> {
+        entries
+            .iter()
+            .filter(|entry| keys.contains(&entry.key))
+            .map(|entry| {
+                (
+                    entry.key,
+                    entry.index.get_untracked(),
+                    entry.delay.get_untracked().as_millis() as u64,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stagger_delay() {
+        let stagger = Stagger::new(Duration::from_millis(50));
+        assert_eq!(stagger.delay(0), Duration::ZERO);
+        assert_eq!(stagger.delay(3), Duration::from_millis(150));
+    }
+
+    #[test]
+    fn stagger_max() {
+        let stagger = Stagger {
+            max: Some(Duration::from_millis(120)),
+            ..Stagger::new(Duration::from_millis(50))
+        };
+        assert_eq!(stagger.delay(2), Duration::from_millis(100));
+        assert_eq!(stagger.delay(3), Duration::from_millis(120));
+        assert_eq!(stagger.delay(usize::MAX), Duration::from_millis(120));
+    }
+
+    #[test]
+    fn diff_keeps_removed_entries_in_place() {
+        let stagger = Stagger::new(Duration::from_millis(10));
+        let prev = diff(&[], vec![1, 2, 3, 4], &|item| *item, &stagger, false);
+        let next = diff(&prev, vec![5, 2, 4, 6], &|item| *item, &stagger, true);
+        assert_eq!(
+            shown(&next),
+            [
+                (1, false),
+                (5, true),
+                (2, true),
+                (3, false),
+                (4, true),
+                (6, true)
+            ]
+        );
+        assert_eq!(
+            staggered(&next, &[1, 3, 5, 6]),
+            [(1, 0, 0), (5, 0, 0), (3, 1, 10), (6, 1, 10)]
+        );
+        assert_eq!(prev[1].id, next[2].id);
+        assert!(next[1].appear);
+    }
+
+    #[test]
+    fn diff_reverse_order() {
+        let stagger = Stagger {
+            order: StaggerOrder::Reverse,
+            ..Stagger::new(Duration::from_millis(10))
+        };
+        let prev = diff(&[], vec![1, 2, 3], &|item| *item, &stagger, true);
+        assert_eq!(shown(&prev), [(1, true), (2, true), (3, true)]);
+        assert_eq!(
+            staggered(&prev, &[1, 2, 3]),
+            [(1, 2, 20), (2, 1, 10), (3, 0, 0)]
+        );
+
+        let next = diff(&prev, vec![2], &|item| *item, &stagger, true);
+        assert_eq!(shown(&next), [(1, false), (2, true), (3, false)]);
+        assert_eq!(staggered(&next, &[1, 3]), [(1, 1, 10), (3, 0, 0)]);
+    }
+}
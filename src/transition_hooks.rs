@@ -0,0 +1,279 @@
+use crate::{utils::ArcOneCallback, TransitionEvent};
+use std::{ops::Deref, sync::Arc};
+use web_sys::Element;
+
+/// JavaScript hooks of the transition lifecycle. All methods do nothing by
+/// default, so an implementation only overrides the ones it needs.
+///
+/// Hooks can be composed by passing a tuple of hooks, which are called in
+/// order.
+///
+/// ``` rust
+/// use leptos::prelude::*;
+/// use web_sys::{wasm_bindgen::JsCast, HtmlElement};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+///
+/// struct FocusFirstInput;
+///
+/// impl TransitionHooks for FocusFirstInput {
+///     fn after_enter(&self, event: &TransitionEvent) {
+///         if let Ok(Some(input)) = event.el.query_selector("input") {
+///             let _ = input.unchecked_into::<HtmlElement>().focus();
+///         }
+///     }
+/// }
+///
+/// let show = RwSignal::new(false);
+/// view! {
+///     <CSSTransition show=show name="fade" hooks=FocusFirstInput>
+///         <div><input /></div>
+///     </CSSTransition>
+/// }
+/// # }
+/// ```
+pub trait TransitionHooks: Send + Sync {
+    /// Called before the enter classes are applied.
+    fn before_enter(&self, _event: &TransitionEvent) {}
+    /// Called one frame after the enter classes are applied, when the enter
+    /// transition starts.
+    fn enter(&self, _event: &TransitionEvent) {}
+    /// Called when the enter transition has finished.
+    fn after_enter(&self, _event: &TransitionEvent) {}
+    /// Called when the enter transition is interrupted by a leave.
+    fn enter_cancelled(&self, _event: &TransitionEvent) {}
+    /// Called before the leave classes are applied.
+    fn before_leave(&self, _event: &TransitionEvent) {}
+    /// Called one frame after the leave classes are applied, when the leave
+    /// transition starts.
+    fn leave(&self, _event: &TransitionEvent) {}
+    /// Called when the leave transition has finished and the element is
+    /// hidden.
+    fn after_leave(&self, _event: &TransitionEvent) {}
+    /// Called when the leave transition is interrupted by an enter.
+    fn leave_cancelled(&self, _event: &TransitionEvent) {}
+}
+
+impl<H: TransitionHooks> TransitionHooks for Option<H> {
+    fn before_enter(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.before_enter(event);
+        }
+    }
+
+    fn enter(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.enter(event);
+        }
+    }
+
+    fn after_enter(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.after_enter(event);
+        }
+    }
+
+    fn enter_cancelled(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.enter_cancelled(event);
+        }
+    }
+
+    fn before_leave(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.before_leave(event);
+        }
+    }
+
+    fn leave(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.leave(event);
+        }
+    }
+
+    fn after_leave(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.after_leave(event);
+        }
+    }
+
+    fn leave_cancelled(&self, event: &TransitionEvent) {
+        if let Some(hooks) = self {
+            hooks.leave_cancelled(event);
+        }
+    }
+}
+
+impl<H: TransitionHooks + ?Sized> TransitionHooks for Arc<H> {
+    fn before_enter(&self, event: &TransitionEvent) {
+        (**self).before_enter(event);
+    }
+
+    fn enter(&self, event: &TransitionEvent) {
+        (**self).enter(event);
+    }
+
+    fn after_enter(&self, event: &TransitionEvent) {
+        (**self).after_enter(event);
+    }
+
+    fn enter_cancelled(&self, event: &TransitionEvent) {
+        (**self).enter_cancelled(event);
+    }
+
+    fn before_leave(&self, event: &TransitionEvent) {
+        (**self).before_leave(event);
+    }
+
+    fn leave(&self, event: &TransitionEvent) {
+        (**self).leave(event);
+    }
+
+    fn after_leave(&self, event: &TransitionEvent) {
+        (**self).after_leave(event);
+    }
+
+    fn leave_cancelled(&self, event: &TransitionEvent) {
+        (**self).leave_cancelled(event);
+    }
+}
+
+macro_rules! impl_transition_hooks_for_tuple {
+    ($($ty:ident),*) => {
+        impl<$($ty: TransitionHooks),*> TransitionHooks for ($($ty,)*) {
+            #[allow(non_snake_case)]
+            fn before_enter(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.before_enter(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn enter(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.enter(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn after_enter(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.after_enter(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn enter_cancelled(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.enter_cancelled(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn before_leave(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.before_leave(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn leave(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.leave(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn after_leave(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.after_leave(event);)*
+            }
+
+            #[allow(non_snake_case)]
+            fn leave_cancelled(&self, event: &TransitionEvent) {
+                let ($($ty,)*) = self;
+                $($ty.leave_cancelled(event);)*
+            }
+        }
+    };
+}
+
+impl_transition_hooks_for_tuple!(A);
+impl_transition_hooks_for_tuple!(A, B);
+impl_transition_hooks_for_tuple!(A, B, C);
+impl_transition_hooks_for_tuple!(A, B, C, D);
+impl_transition_hooks_for_tuple!(A, B, C, D, E);
+impl_transition_hooks_for_tuple!(A, B, C, D, E, F);
+
+/// A shared, type-erased [`TransitionHooks`].
+#[derive(Clone)]
+pub struct ArcTransitionHooks(Arc<dyn TransitionHooks>);
+
+impl ArcTransitionHooks {
+    pub fn new<H>(hooks: H) -> Self
+    where
+        H: TransitionHooks + 'static,
+    {
+        Self(Arc::new(hooks))
+    }
+}
+
+impl Deref for ArcTransitionHooks {
+    type Target = Arc<dyn TransitionHooks>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<H> From<H> for ArcTransitionHooks
+where
+    H: TransitionHooks + 'static,
+{
+    fn from(value: H) -> Self {
+        Self::new(value)
+    }
+}
+
+/// The `on_*` callback props of `CSSTransition`.
+#[derive(Default)]
+pub(crate) struct CallbackHooks {
+    pub on_before_enter: Option<ArcOneCallback<Element>>,
+    pub on_enter: Option<ArcOneCallback<Element>>,
+    pub on_after_enter: Option<ArcOneCallback<Element>>,
+    pub on_before_leave: Option<ArcOneCallback<Element>>,
+    pub on_leave: Option<ArcOneCallback<Element>>,
+    pub on_after_leave: Option<ArcOneCallback<Element>>,
+}
+
+impl TransitionHooks for CallbackHooks {
+    fn before_enter(&self, event: &TransitionEvent) {
+        if let Some(on_before_enter) = self.on_before_enter.as_ref() {
+            on_before_enter(event.el.clone());
+        }
+    }
+
+    fn enter(&self, event: &TransitionEvent) {
+        if let Some(on_enter) = self.on_enter.as_ref() {
+            on_enter(event.el.clone());
+        }
+    }
+
+    fn after_enter(&self, event: &TransitionEvent) {
+        if let Some(on_after_enter) = self.on_after_enter.as_ref() {
+            on_after_enter(event.el.clone());
+        }
+    }
+
+    fn before_leave(&self, event: &TransitionEvent) {
+        if let Some(on_before_leave) = self.on_before_leave.as_ref() {
+            on_before_leave(event.el.clone());
+        }
+    }
+
+    fn leave(&self, event: &TransitionEvent) {
+        if let Some(on_leave) = self.on_leave.as_ref() {
+            on_leave(event.el.clone());
+        }
+    }
+
+    fn after_leave(&self, event: &TransitionEvent) {
+        if let Some(on_after_leave) = self.on_after_leave.as_ref() {
+            on_after_leave(event.el.clone());
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{get_transition_info::get_transition_info, CSSTransitionInfo};
+use leptos::prelude::document;
+use std::{cell::RefCell, collections::HashMap};
+use web_sys::{
+    js_sys::Array,
+    wasm_bindgen::{closure::Closure, JsCast},
+    Element, MutationObserver, MutationObserverInit,
+};
+
+/// Elements with the same tag and classes are assumed to compute the same
+/// transition info, as long as the stylesheets don't change. The inline
+/// style isn't part of the key, it changes with every custom property and
+/// `display` toggle.
+#[derive(PartialEq, Eq, Hash)]
+struct CacheKey {
+    tag: String,
+    classes: Vec<String>,
+}
+
+#[derive(Default)]
+struct TransitionInfoCache {
+    entries: HashMap<CacheKey, Option<CSSTransitionInfo>>,
+    // Clears the entries when a stylesheet in `<head>` changes.
+    observer: Option<StylesheetObserver>,
+}
+
+type StylesheetObserver = (
+    MutationObserver,
+    Closure<dyn FnMut(Array, MutationObserver)>,
+);
+
+thread_local! {
+    static CACHE: RefCell<TransitionInfoCache> = Default::default();
+}
+
+/// Same as [`get_transition_info`], but reuses the result computed for an
+/// element with the same tag and classes.
+///
+/// Only changes in `<head>` invalidate the cache. Durations that change
+/// with a media query, an ancestor's class or an inline style are not
+/// picked up.
+pub(crate) fn get_transition_info_cached(el: &Element) -> Option<CSSTransitionInfo> {
+    let mut classes = el
+        .get_attribute("class")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|class| class.to_string())
+        .collect::<Vec<_>>();
+    classes.sort();
+    let key = CacheKey {
+        tag: el.tag_name(),
+        classes,
+    };
+
+    if let Some(info) = CACHE.with_borrow(|cache| cache.entries.get(&key).copied()) {
+        return info;
+    }
+
+    let info = get_transition_info(el);
+    CACHE.with_borrow_mut(|cache| {
+        if cache.observer.is_none() {
+            cache.observer = observe_stylesheets();
+        }
+        cache.entries.insert(key, info);
+    });
+    info
+}
+
+/// Clears the cache of `cache_info`, e.g. after changing a stylesheet
+/// outside `<head>`, or when a media query or an ancestor changes the
+/// durations.
+pub fn clear_transition_info_cache() {
+    CACHE.with_borrow_mut(|cache| cache.entries.clear());
+}
+
+fn observe_stylesheets() -> Option<StylesheetObserver> {
+    let head = document().head()?;
+    let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new(|_, _| {
+        clear_transition_info_cache();
+    });
+    let observer = MutationObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+    let options = MutationObserverInit::new();
+    options.set_child_list(true);
+    options.set_subtree(true);
+    options.set_attributes(true);
+    options.set_character_data(true);
+    observer.observe_with_options(&head, &options).ok()?;
+    Some((observer, callback))
+}
@@ -0,0 +1,769 @@
+use crate::{AnimationTypes, CSSTransitionInfo, TransitionPhase};
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+/// A point of the transition lifecycle, see
+/// [`TransitionHooks`](crate::TransitionHooks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionHook {
+    BeforeEnter,
+    Enter,
+    AfterEnter,
+    EnterCancelled,
+    BeforeLeave,
+    Leave,
+    AfterLeave,
+    LeaveCancelled,
+}
+
+/// The state of the transition reported with a [`TransitionHook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionContext {
+    pub phase: TransitionPhase,
+    /// Whether this is the enter transition of the initial render.
+    pub appear: bool,
+    /// Whether this transition interrupted the previous one.
+    pub interrupted: bool,
+    /// `None` before the `*-to` classes are applied, or if the element has
+    /// no transition or animation.
+    pub info: Option<CSSTransitionInfo>,
+}
+
+/// How a transition gets the `-from` classes rendered before swapping them
+/// for the `-to` classes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StartStrategy {
+    /// Waits two animation frames. Adds up to two frames of latency and
+    /// stalls in background tabs, but never forces a layout.
+    #[default]
+    DoubleFrame,
+    /// Forces a synchronous reflow and swaps the classes in the same task,
+    /// like Vue. Starts without latency, at the cost of a layout per
+    /// transition.
+    Reflow,
+}
+
+/// A step of the class sequence, with its `-from`, `-active` or `-to` class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionStage {
+    From,
+    Active,
+    To,
+}
+
+/// The outside world of a [`TransitionMachine`]: the element it animates
+/// and the clock it runs on.
+///
+/// `CSSTransition` uses a backend on top of `web_sys`. The `testing` feature
+/// provides an in-memory one, which also runs on the host.
+pub trait TransitionBackend: 'static {
+    /// A scheduled callback or an end listener.
+    type Handle;
+
+    /// Adds the classes in a single class list change.
+    fn add_classes(&self, classes: &[&str]);
+    /// Removes the classes in a single class list change.
+    fn remove_classes(&self, classes: &[&str]);
+    /// Called along with the class changes, with the stages whose classes
+    /// were removed and added, for backends that apply more than classes.
+    fn change_stages(
+        &self,
+        _phase: TransitionPhase,
+        _removed: &[TransitionStage],
+        _added: &[TransitionStage],
+    ) {
+    }
+    /// Shows or hides the element with `display: none`.
+    fn set_visible(&self, visible: bool);
+    /// Reads the transitions or animations the element currently has.
+    fn transition_info(&self) -> Option<CSSTransitionInfo>;
+    /// Forces the pending style changes to be applied, see
+    /// [`StartStrategy::Reflow`].
+    fn force_reflow(&self) {}
+
+    /// Runs `cb` in the next frame, where DOM writes go.
+    fn request_frame(&self, cb: Box<dyn FnOnce()>) -> Self::Handle;
+    /// Runs `cb` after the writes of the current frame, where DOM reads go.
+    fn request_read(&self, cb: Box<dyn FnOnce()>) -> Self::Handle;
+    fn set_timeout(&self, cb: Box<dyn FnOnce()>, delay: Duration) -> Self::Handle;
+    /// Calls `cb` on every `transitionend` or `animationend` event of the
+    /// element itself, not of its descendants.
+    fn listen_end(&self, types: AnimationTypes, cb: Box<dyn Fn()>) -> Self::Handle;
+    /// Cancels a scheduled callback or removes an end listener.
+    fn cancel(&self, handle: Self::Handle);
+
+    /// Reports a lifecycle point.
+    fn notify(&self, hook: TransitionHook, context: &TransitionContext);
+
+    /// Identifies the element in `tracing` spans.
+    fn describe(&self) -> String {
+        String::new()
+    }
+}
+
+/// The enter and leave lifecycle of a single element, free of DOM and
+/// timer APIs.
+///
+/// A transition adds `{name}-{phase}-from` and `{name}-{phase}-active`,
+/// waits for them to be rendered (after the optional delay) as set by the
+/// [`StartStrategy`], swaps `-from` for `-to`, then waits for the end events
+/// or the computed timeout before removing the classes.
+pub struct TransitionMachine<B: TransitionBackend> {
+    inner: Rc<MachineInner<B>>,
+}
+
+impl<B: TransitionBackend> Clone for TransitionMachine<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+struct MachineInner<B: TransitionBackend> {
+    backend: B,
+    start_strategy: Cell<StartStrategy>,
+    state: RefCell<MachineState<B::Handle>>,
+}
+
+struct MachineState<H> {
+    // Increased by every transition, so stale callbacks can be ignored.
+    generation: u64,
+    running: Option<Running>,
+    handles: Vec<H>,
+}
+
+struct Running {
+    generation: u64,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    context: TransitionContext,
+    from: String,
+    active: String,
+    to: String,
+    // Whether `-from` has been swapped for `-to`.
+    started: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FinishReason {
+    /// The `transitionend` or `animationend` events of all properties.
+    EndEvent,
+    /// The computed timeout elapsed before the end events.
+    Timeout,
+    /// The element has no transition or animation.
+    NoTransition,
+    /// Completed by [`TransitionMachine::complete`].
+    Completed,
+    /// Interrupted by the opposite transition or
+    /// [`TransitionMachine::cancel`].
+    Cancelled,
+}
+
+impl<B: TransitionBackend> TransitionMachine<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            inner: Rc::new(MachineInner {
+                backend,
+                start_strategy: Cell::new(StartStrategy::default()),
+                state: RefCell::new(MachineState {
+                    generation: 0,
+                    running: None,
+                    handles: vec![],
+                }),
+            }),
+        }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.inner.backend
+    }
+
+    /// Sets how the following transitions start.
+    pub fn set_start_strategy(&self, start_strategy: StartStrategy) {
+        self.inner.start_strategy.set(start_strategy);
+    }
+
+    /// Whether a transition is running.
+    pub fn is_running(&self) -> bool {
+        self.inner.state.borrow().running.is_some()
+    }
+
+    /// The phase of the running transition.
+    pub fn phase(&self) -> Option<TransitionPhase> {
+        self.inner
+            .state
+            .borrow()
+            .running
+            .as_ref()
+            .map(|running| running.context.phase)
+    }
+
+    /// Starts the enter transition, interrupting the running one.
+    pub fn enter(&self, name: &str, delay: Duration, appear: bool) {
+        self.start(TransitionPhase::Enter, name, delay, appear);
+    }
+
+    /// Starts the leave transition, interrupting the running one.
+    pub fn leave(&self, name: &str, delay: Duration) {
+        self.start(TransitionPhase::Leave, name, delay, false);
+    }
+
+    /// Interrupts the running transition, reporting `*Cancelled`. Returns
+    /// whether a transition was running.
+    pub fn cancel(&self) -> bool {
+        self.finish(None, FinishReason::Cancelled)
+    }
+
+    /// Jumps to the end of the running transition, as if it had ended
+    /// naturally.
+    pub fn complete(&self) {
+        self.finish(None, FinishReason::Completed);
+    }
+
+    /// Drops the running transition without reporting anything.
+    pub fn stop(&self) {
+        let handles = {
+            let mut state = self.inner.state.borrow_mut();
+            state.generation += 1;
+            if let Some(_running) = state.running.take() {
+                trace!(parent: &_running.span, "transition stopped");
+            }
+            state.handles.drain(..).collect::<Vec<_>>()
+        };
+        for handle in handles {
+            self.inner.backend.cancel(handle);
+        }
+    }
+
+    fn start(&self, phase: TransitionPhase, name: &str, delay: Duration, appear: bool) {
+        let interrupted = self.cancel();
+        let backend = &self.inner.backend;
+
+        let context = TransitionContext {
+            phase,
+            appear,
+            interrupted,
+            info: None,
+        };
+        match phase {
+            TransitionPhase::Enter => backend.notify(TransitionHook::BeforeEnter, &context),
+            TransitionPhase::Leave => backend.notify(TransitionHook::BeforeLeave, &context),
+        }
+
+        let generation = {
+            let mut state = self.inner.state.borrow_mut();
+            state.generation += 1;
+            state.generation
+        };
+        let running = Running {
+            generation,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                "transition",
+                element = %backend.describe(),
+                name,
+                %phase,
+                appear,
+                interrupted,
+                ?delay,
+            ),
+            context,
+            from: format!("{name}-{phase}-from"),
+            active: format!("{name}-{phase}-active"),
+            to: format!("{name}-{phase}-to"),
+            started: false,
+        };
+
+        // from active
+        backend.add_classes(&[&running.from, &running.active]);
+        backend.change_stages(
+            phase,
+            &[],
+            &[TransitionStage::From, TransitionStage::Active],
+        );
+        trace!(
+            parent: &running.span,
+            added = ?[&running.from, &running.active],
+            "transition scheduled"
+        );
+        if phase == TransitionPhase::Enter {
+            backend.set_visible(true);
+        }
+        self.inner.state.borrow_mut().running = Some(running);
+
+        let next_frame = match self.inner.start_strategy.get() {
+            StartStrategy::DoubleFrame => self.callback(generation, move |machine| {
+                let frame = machine.callback(generation, move |machine| {
+                    let frame = machine.callback(generation, |machine| machine.on_frame(false));
+                    machine.schedule(|backend| backend.request_frame(frame));
+                });
+                machine.schedule(|backend| backend.request_frame(frame));
+            }),
+            StartStrategy::Reflow => self.callback(generation, |machine| {
+                machine.inner.backend.force_reflow();
+                machine.on_frame(true);
+            }),
+        };
+        if delay.is_zero() {
+            next_frame();
+        } else {
+            self.schedule(|backend| backend.set_timeout(next_frame, delay));
+        }
+    }
+
+    // The `-from` classes have been rendered, swaps them for `-to`. The
+    // started transition is read right away if `read_now`, otherwise after
+    // the writes of the frame.
+    fn on_frame(&self, read_now: bool) {
+        let backend = &self.inner.backend;
+        let generation = {
+            let mut state = self.inner.state.borrow_mut();
+            let Some(running) = state.running.as_mut() else {
+                return;
+            };
+            running.started = true;
+
+            // active to
+            backend.remove_classes(&[&running.from]);
+            backend.add_classes(&[&running.to]);
+            backend.change_stages(
+                running.context.phase,
+                &[TransitionStage::From],
+                &[TransitionStage::To],
+            );
+            trace!(
+                parent: &running.span,
+                removed = %running.from,
+                added = %running.to,
+                "classes swapped"
+            );
+            running.generation
+        };
+
+        if read_now {
+            self.on_started();
+        } else {
+            let on_started = self.callback(generation, |machine| machine.on_started());
+            self.schedule(|backend| backend.request_read(on_started));
+        }
+    }
+
+    // Reads the transition the `-to` classes have started.
+    fn on_started(&self) {
+        let backend = &self.inner.backend;
+        let (generation, context) = {
+            let mut state = self.inner.state.borrow_mut();
+            let Some(running) = state.running.as_mut() else {
+                return;
+            };
+            running.context.info = backend.transition_info();
+            trace!(
+                parent: &running.span,
+                types = ?running.context.info.map(|info| info.types),
+                prop_count = running.context.info.map(|info| info.prop_count),
+                timeout = running.context.info.map(|info| info.timeout),
+                "transition started"
+            );
+            (running.generation, running.context)
+        };
+
+        match context.phase {
+            TransitionPhase::Enter => backend.notify(TransitionHook::Enter, &context),
+            TransitionPhase::Leave => backend.notify(TransitionHook::Leave, &context),
+        }
+
+        let Some(info) = context.info else {
+            self.finish(Some(generation), FinishReason::NoTransition);
+            return;
+        };
+
+        let weak = Rc::downgrade(&self.inner);
+        let handles = wait_for_end(backend, info, move |timed_out| {
+            if let Some(machine) = Self::upgrade(&weak) {
+                let reason = if timed_out {
+                    FinishReason::Timeout
+                } else {
+                    FinishReason::EndEvent
+                };
+                machine.finish(Some(generation), reason);
+            }
+        });
+        self.inner.state.borrow_mut().handles.extend(handles);
+    }
+
+    // Finishes the running transition, or only the one of `generation`.
+    fn finish(&self, generation: Option<u64>, reason: FinishReason) -> bool {
+        let backend = &self.inner.backend;
+        let (running, handles) = {
+            let mut state = self.inner.state.borrow_mut();
+            match (state.running.as_ref(), generation) {
+                (None, _) => return false,
+                (Some(running), Some(generation)) if running.generation != generation => {
+                    return false;
+                }
+                _ => {}
+            }
+            let handles = state.handles.drain(..).collect::<Vec<_>>();
+            (state.running.take(), handles)
+        };
+        for handle in handles {
+            backend.cancel(handle);
+        }
+        let Some(running) = running else {
+            return false;
+        };
+
+        let (removed, stages) = if running.started {
+            (
+                [&running.active, &running.to],
+                [TransitionStage::Active, TransitionStage::To],
+            )
+        } else {
+            (
+                [&running.from, &running.active],
+                [TransitionStage::From, TransitionStage::Active],
+            )
+        };
+        backend.remove_classes(&removed.map(String::as_str));
+        backend.change_stages(running.context.phase, &stages, &[]);
+        trace!(
+            parent: &running.span,
+            ?reason,
+            ?removed,
+            "transition finished"
+        );
+
+        let cancelled = reason == FinishReason::Cancelled;
+        let hook = match (running.context.phase, cancelled) {
+            (TransitionPhase::Enter, false) => TransitionHook::AfterEnter,
+            (TransitionPhase::Enter, true) => TransitionHook::EnterCancelled,
+            (TransitionPhase::Leave, false) => {
+                backend.set_visible(false);
+                TransitionHook::AfterLeave
+            }
+            (TransitionPhase::Leave, true) => TransitionHook::LeaveCancelled,
+        };
+        backend.notify(hook, &running.context);
+
+        true
+    }
+
+    fn schedule(&self, f: impl FnOnce(&B) -> B::Handle) {
+        let handle = f(&self.inner.backend);
+        self.inner.state.borrow_mut().handles.push(handle);
+    }
+
+    // Wraps `f` so it does nothing once the machine is dropped or the
+    // transition of `generation` is over.
+    fn callback(&self, generation: u64, f: impl FnOnce(&Self) + 'static) -> Box<dyn FnOnce()> {
+        let weak = Rc::downgrade(&self.inner);
+        Box::new(move || {
+            let Some(machine) = Self::upgrade(&weak) else {
+                return;
+            };
+            if machine.inner.state.borrow().generation == generation {
+                f(&machine);
+            }
+        })
+    }
+
+    fn upgrade(weak: &Weak<MachineInner<B>>) -> Option<Self> {
+        weak.upgrade().map(|inner| Self { inner })
+    }
+}
+
+/// Calls `on_end` once the element has fired the end events of all its
+/// transitioned properties or animations, or once the computed timeout has
+/// elapsed, with whether the timeout elapsed first. Returns the handles of
+/// the timeout and the end listener.
+pub(crate) fn wait_for_end<B: TransitionBackend>(
+    backend: &B,
+    info: CSSTransitionInfo,
+    on_end: impl FnOnce(bool) + 'static,
+) -> [B::Handle; 2] {
+    let on_end = Rc::new(Cell::new(Some(on_end)));
+
+    let timeout = backend.set_timeout(
+        Box::new({
+            let on_end = on_end.clone();
+            move || {
+                if let Some(on_end) = on_end.take() {
+                    on_end(true);
+                }
+            }
+        }),
+        Duration::from_millis(info.timeout.saturating_add(1)),
+    );
+
+    let end_count = Cell::new(0);
+    let listener = backend.listen_end(
+        info.types,
+        Box::new(move || {
+            end_count.set(end_count.get() + 1);
+            if end_count.get() >= info.prop_count {
+                if let Some(on_end) = on_end.take() {
+                    on_end(false);
+                }
+            }
+        }),
+    );
+
+    [timeout, listener]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MemoryBackend, MockScheduler, StageChange};
+    use TransitionHook::*;
+    use TransitionStage::*;
+
+    fn machine(duration: Option<Duration>) -> (MockScheduler, TransitionMachine<MemoryBackend>) {
+        let scheduler = MockScheduler::new();
+        let machine = TransitionMachine::new(MemoryBackend::new(scheduler.clone()));
+        machine.backend().set_transition_info(duration);
+        (scheduler, machine)
+    }
+
+    const DURATION: Option<Duration> = Some(Duration::from_millis(300));
+
+    #[test]
+    fn enter_and_leave() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        assert_eq!(backend.classes(), ["fade-enter-from", "fade-enter-active"]);
+        assert!(backend.is_visible());
+        scheduler.advance_frame();
+        assert_eq!(backend.classes(), ["fade-enter-from", "fade-enter-active"]);
+        scheduler.advance_frame();
+        assert_eq!(backend.classes(), ["fade-enter-active", "fade-enter-to"]);
+        assert_eq!(backend.take_hooks(), [BeforeEnter, Enter]);
+
+        backend.fire_end();
+        assert!(backend.classes().is_empty());
+        assert!(!machine.is_running());
+        assert_eq!(backend.take_hooks(), [AfterEnter]);
+        assert_eq!(
+            backend.take_stage_changes(),
+            [
+                StageChange::new(TransitionPhase::Enter, &[], &[From, Active]),
+                StageChange::new(TransitionPhase::Enter, &[From], &[To]),
+                StageChange::new(TransitionPhase::Enter, &[Active, To], &[]),
+            ]
+        );
+
+        machine.leave("fade", Duration::ZERO);
+        assert_eq!(machine.phase(), Some(TransitionPhase::Leave));
+        scheduler.advance_frames(2);
+        assert_eq!(backend.classes(), ["fade-leave-active", "fade-leave-to"]);
+        assert!(backend.is_visible());
+        backend.fire_end();
+        assert!(backend.classes().is_empty());
+        assert!(!backend.is_visible());
+        assert_eq!(backend.take_hooks(), [BeforeLeave, Leave, AfterLeave]);
+        assert_eq!(
+            backend.take_stage_changes(),
+            [
+                StageChange::new(TransitionPhase::Leave, &[], &[From, Active]),
+                StageChange::new(TransitionPhase::Leave, &[From], &[To]),
+                StageChange::new(TransitionPhase::Leave, &[Active, To], &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn enter_interrupted_by_leave() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        scheduler.advance_frames(2);
+        backend.take_hooks();
+        machine.leave("fade", Duration::ZERO);
+        assert_eq!(backend.classes(), ["fade-leave-from", "fade-leave-active"]);
+
+        let hooks = backend.take_hooks_with_context();
+        assert_eq!(
+            hooks.iter().map(|(hook, _)| *hook).collect::<Vec<_>>(),
+            [EnterCancelled, BeforeLeave]
+        );
+        assert!(!hooks[0].1.interrupted);
+        assert!(hooks[1].1.interrupted);
+        assert_eq!(
+            &backend.take_stage_changes()[2..],
+            [
+                StageChange::new(TransitionPhase::Enter, &[Active, To], &[]),
+                StageChange::new(TransitionPhase::Leave, &[], &[From, Active]),
+            ]
+        );
+
+        // The timeout of the cancelled enter doesn't finish the leave.
+        scheduler.advance_by(Duration::from_millis(301));
+        assert_eq!(machine.phase(), Some(TransitionPhase::Leave));
+        assert!(backend.take_hooks().is_empty());
+    }
+
+    #[test]
+    fn delay() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::from_millis(100), false);
+        scheduler.advance_frames(2);
+        assert_eq!(backend.classes(), ["fade-enter-from", "fade-enter-active"]);
+        scheduler.advance_by(Duration::from_millis(99));
+        scheduler.advance_frames(2);
+        assert_eq!(backend.classes(), ["fade-enter-from", "fade-enter-active"]);
+
+        scheduler.advance_by(Duration::from_millis(1));
+        scheduler.advance_frames(2);
+        assert_eq!(backend.classes(), ["fade-enter-active", "fade-enter-to"]);
+        assert_eq!(backend.take_hooks(), [BeforeEnter, Enter]);
+    }
+
+    #[test]
+    fn timeout_before_end_event() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        scheduler.advance_frames(2);
+        scheduler.advance_by(Duration::from_millis(300));
+        assert!(machine.is_running());
+        scheduler.advance_by(Duration::from_millis(1));
+        assert!(!machine.is_running());
+        assert!(backend.classes().is_empty());
+        assert_eq!(backend.take_hooks(), [BeforeEnter, Enter, AfterEnter]);
+
+        // A late end event is ignored.
+        backend.fire_end();
+        assert!(backend.take_hooks().is_empty());
+    }
+
+    #[test]
+    fn end_event_before_timeout() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        scheduler.advance_frames(2);
+        assert_eq!(scheduler.pending_timeouts(), 1);
+        backend.fire_end();
+        assert_eq!(scheduler.pending_timeouts(), 0);
+        assert_eq!(backend.take_hooks(), [BeforeEnter, Enter, AfterEnter]);
+    }
+
+    #[test]
+    fn huge_timeout() {
+        let (scheduler, machine) = machine(Some(Duration::from_millis(u64::MAX)));
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        scheduler.advance_frames(2);
+        assert_eq!(scheduler.pending_timeouts(), 1);
+        backend.fire_end();
+        assert!(!machine.is_running());
+    }
+
+    #[test]
+    fn no_transition() {
+        let (scheduler, machine) = machine(None);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, true);
+        scheduler.advance_frames(2);
+        assert!(!machine.is_running());
+        assert!(backend.classes().is_empty());
+        let hooks = backend.take_hooks_with_context();
+        assert_eq!(
+            hooks.iter().map(|(hook, _)| *hook).collect::<Vec<_>>(),
+            [BeforeEnter, Enter, AfterEnter]
+        );
+        assert!(hooks
+            .iter()
+            .all(|(_, context)| context.appear && context.info.is_none()));
+    }
+
+    #[test]
+    fn complete() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.leave("fade", Duration::ZERO);
+        machine.complete();
+        assert!(backend.classes().is_empty());
+        assert!(!backend.is_visible());
+        assert_eq!(backend.take_hooks(), [BeforeLeave, AfterLeave]);
+        assert_eq!(scheduler.pending_frames(), 0);
+    }
+
+    #[test]
+    fn cancel() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        assert!(!machine.cancel());
+        machine.leave("fade", Duration::ZERO);
+        scheduler.advance_frames(2);
+        assert!(machine.cancel());
+        assert!(backend.classes().is_empty());
+        assert!(backend.is_visible());
+        assert_eq!(backend.take_hooks(), [BeforeLeave, Leave, LeaveCancelled]);
+        assert_eq!(scheduler.pending_timeouts(), 0);
+        assert!(!machine.cancel());
+    }
+
+    #[test]
+    fn cancel_before_swap() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::ZERO, false);
+        scheduler.advance_frame();
+        assert!(machine.cancel());
+        assert!(backend.classes().is_empty());
+        assert_eq!(backend.take_hooks(), [BeforeEnter, EnterCancelled]);
+        assert_eq!(
+            backend.take_stage_changes(),
+            [
+                StageChange::new(TransitionPhase::Enter, &[], &[From, Active]),
+                StageChange::new(TransitionPhase::Enter, &[From, Active], &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.enter("fade", Duration::from_millis(100), false);
+        backend.take_hooks();
+        machine.stop();
+        assert!(!machine.is_running());
+        assert_eq!(scheduler.pending_timeouts(), 0);
+        scheduler.advance_by(Duration::from_millis(100));
+        scheduler.advance_frames(2);
+        assert!(backend.take_hooks().is_empty());
+        // The classes are left as they are.
+        assert_eq!(backend.classes(), ["fade-enter-from", "fade-enter-active"]);
+    }
+
+    #[test]
+    fn reflow_start_strategy() {
+        let (scheduler, machine) = machine(DURATION);
+        let backend = machine.backend();
+
+        machine.set_start_strategy(StartStrategy::Reflow);
+        machine.enter("fade", Duration::ZERO, false);
+        assert_eq!(backend.classes(), ["fade-enter-active", "fade-enter-to"]);
+        assert_eq!(backend.take_hooks(), [BeforeEnter, Enter]);
+        assert_eq!(scheduler.pending_frames(), 0);
+    }
+}
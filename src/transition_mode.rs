@@ -0,0 +1,10 @@
+/// The order of the leave and enter transitions when one content replaces
+/// another.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionMode {
+    /// The leave and enter transitions run at the same time.
+    #[default]
+    Simultaneous,
+    /// The current content leaves first, then the new content enters.
+    OutIn,
+}
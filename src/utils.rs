@@ -3,7 +3,7 @@ use send_wrapper::SendWrapper;
 use std::{ops::Deref, sync::Arc};
 use web_sys::{
     wasm_bindgen::{prelude::Closure, JsCast},
-    EventTarget,
+    CssStyleDeclaration, EventTarget, HtmlElement,
 };
 
 #[derive(Clone)]
@@ -63,7 +63,7 @@ impl EventListenerHandle {
     }
 }
 
-fn add_event_listener_untyped(
+pub(crate) fn add_event_listener_untyped(
     target: impl Into<EventTarget>,
     event_name: &str,
     cb: impl Fn(web_sys::Event) + 'static,
@@ -90,13 +90,61 @@ fn add_event_listener_untyped(
     wel(target.into(), Box::new(cb), event_name)
 }
 
+/// The inline style of an HTML, SVG or MathML element.
+pub fn element_style(el: &web_sys::Element) -> Option<CssStyleDeclaration> {
+    if let Some(el) = el.dyn_ref::<web_sys::HtmlElement>() {
+        Some(el.style())
+    } else if let Some(el) = el.dyn_ref::<web_sys::SvgElement>() {
+        Some(el.style())
+    } else {
+        el.dyn_ref::<web_sys::MathMlElement>().map(|el| el.style())
+    }
+}
+
+/// The layout box of an element, unaffected by transforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn measure(el: &HtmlElement) -> Self {
+        Self {
+            left: el.offset_left().into(),
+            top: el.offset_top().into(),
+            width: el.offset_width().into(),
+            height: el.offset_height().into(),
+        }
+    }
+}
+
+/// Takes the element out of the flow with `position: absolute`, drawn
+/// where `rect` was laid out in its offset parent.
+pub fn pin_in_place(el: &HtmlElement, rect: Rect) {
+    let style = el.style();
+    for (property, value) in [
+        ("position", "absolute".to_string()),
+        ("left", format!("{}px", rect.left)),
+        ("top", format!("{}px", rect.top)),
+        ("width", format!("{}px", rect.width)),
+        ("height", format!("{}px", rect.height)),
+        ("margin", "0".to_string()),
+        ("box-sizing", "border-box".to_string()),
+    ] {
+        let _ = style.set_property(property, &value);
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct HtmlElement {
-    el: SendWrapper<web_sys::HtmlElement>,
+pub struct Element {
+    el: SendWrapper<web_sys::Element>,
 }
 
-impl ElementType for HtmlElement {
-    type Output = web_sys::HtmlElement;
+impl ElementType for Element {
+    type Output = web_sys::Element;
 
     const TAG: &'static str = "";
 
@@ -111,8 +159,8 @@ impl ElementType for HtmlElement {
     }
 }
 
-impl Deref for HtmlElement {
-    type Target = web_sys::HtmlElement;
+impl Deref for Element {
+    type Target = web_sys::Element;
 
     fn deref(&self) -> &Self::Target {
         &self.el
@@ -0,0 +1,137 @@
+use leptos::{prelude::document, task::tick};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{
+    js_sys::{Function, Promise, Reflect},
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+};
+
+/// Whether the browser supports `document.startViewTransition`.
+pub fn is_view_transition_supported() -> bool {
+    start_view_transition_fn().is_some()
+}
+
+fn start_view_transition_fn() -> Option<Function> {
+    Reflect::get(&document(), &JsValue::from_str("startViewTransition"))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()
+}
+
+/// A view transition started by [`start_view_transition`].
+///
+/// If the browser doesn't support view transitions, or the transition
+/// couldn't start, the update has been applied without animation and all
+/// futures resolve immediately.
+#[derive(Debug, Clone)]
+pub struct ViewTransition(Option<JsValue>);
+
+impl ViewTransition {
+    /// Resolves when the pseudo-element tree is created and the animation
+    /// is about to start.
+    pub async fn ready(&self) -> Result<(), JsValue> {
+        self.promise("ready").await
+    }
+
+    /// Resolves when the animation has finished and the new view is
+    /// visible and interactive.
+    pub async fn finished(&self) -> Result<(), JsValue> {
+        self.promise("finished").await
+    }
+
+    /// Resolves when the update has been applied.
+    pub async fn update_callback_done(&self) -> Result<(), JsValue> {
+        self.promise("updateCallbackDone").await
+    }
+
+    /// Skips the animation, the update is still applied.
+    pub fn skip_transition(&self) {
+        let Some(transition) = self.0.as_ref() else {
+            return;
+        };
+        if let Ok(skip) = Reflect::get(transition, &JsValue::from_str("skipTransition"))
+            .and_then(|skip| skip.dyn_into::<Function>())
+        {
+            let _ = skip.call0(transition);
+        }
+    }
+
+    async fn promise(&self, name: &str) -> Result<(), JsValue> {
+        let Some(transition) = self.0.as_ref() else {
+            return Ok(());
+        };
+        let promise = Reflect::get(transition, &JsValue::from_str(name))?.dyn_into::<Promise>()?;
+        JsFuture::from(promise).await.map(|_| ())
+    }
+}
+
+/// Runs `update` inside `document.startViewTransition`, so the browser
+/// morphs the old view into the new one. The new view is captured once the
+/// reactive system has applied the changes made by `update`.
+///
+/// Elements are matched between the two views by their
+/// `view-transition-name` style.
+///
+/// ``` rust
+/// use leptos::{prelude::*, task::spawn_local};
+/// use leptos_transition_group::*;
+/// # fn demo() -> impl IntoView {
+/// let show = RwSignal::new(false);
+/// let toggle = move |_| {
+///     let transition = start_view_transition(move || show.update(|v| *v = !*v));
+///     spawn_local(async move {
+///         let _ = transition.finished().await;
+///     });
+/// };
+/// view! { <button on:click=toggle>"Toggle"</button> }
+/// # }
+/// ```
+pub fn start_view_transition(update: impl FnOnce() + 'static) -> ViewTransition {
+    start_with(update, || {
+        future_to_promise(async move {
+            tick().await;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
+    })
+}
+
+/// Starts a view transition where `update` changes the DOM synchronously.
+pub(crate) fn start_view_transition_sync(update: impl FnOnce() + 'static) -> ViewTransition {
+    start_with(update, || JsValue::UNDEFINED)
+}
+
+/// Calls `document.startViewTransition` with a callback running `update`,
+/// then returning `done()`.
+fn start_with(
+    update: impl FnOnce() + 'static,
+    done: impl FnOnce() -> JsValue + 'static,
+) -> ViewTransition {
+    let Some(start) = start_view_transition_fn() else {
+        update();
+        return ViewTransition(None);
+    };
+
+    let update = Rc::new(Cell::new(Some(update)));
+    let callback = Closure::once_into_js({
+        let update = update.clone();
+        move || {
+            if let Some(update) = update.take() {
+                update();
+            }
+            done()
+        }
+    });
+
+    match start.call1(&document(), &callback) {
+        Ok(transition) => ViewTransition(Some(transition)),
+        // e.g. an `InvalidStateError` when the document is hidden, the
+        // update is still applied.
+        Err(_) => {
+            if let Some(update) = update.take() {
+                update();
+            }
+            ViewTransition(None)
+        }
+    }
+}